@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use watchtower_defence::{board::*, logic::*};
+
+mod baseline;
+
+/// Both watchtowers placed, first draught move of white.
+fn opening() -> BoardState {
+    let mut board = BoardState::new();
+    board.apply(Move::PlaceWatchtower { i: 5, j: 5 }).unwrap();
+    board.apply(Move::PlaceWatchtower { i: 12, j: 12 }).unwrap();
+    board
+}
+
+/// Opening followed by a few rounds of stones building a wall across the board.
+fn middlegame() -> BoardState {
+    let mut board = opening();
+    for n in 0..8 {
+        let mv = board
+            .legal_moves()
+            .into_iter()
+            .find(|mv| matches!(mv, Move::StepDraught { .. }))
+            .unwrap_or(Move::Pass);
+        board.apply(mv).unwrap();
+        board.apply(Move::PlaceStone { i: 2 * n, j: 9 }).unwrap();
+    }
+    board
+}
+
+fn rules(c: &mut Criterion) {
+    for (name, board) in [("opening", opening()), ("middlegame", middlegame())] {
+        c.bench_function(&format!("territory/{name}"), |b| {
+            b.iter(|| black_box(&board).territory(Turn::White))
+        });
+        c.bench_function(&format!("legal_moves/{name}"), |b| {
+            b.iter(|| black_box(&board).legal_moves())
+        });
+    }
+}
+
+/// The vector implementation the bitboards replaced, on the same positions. It takes from a
+/// tenth of a second to a second per call, hence the few samples.
+fn baseline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("baseline");
+    group.sample_size(10);
+    for (name, board) in [("opening", opening()), ("middlegame", middlegame())] {
+        let stones: Vec<(usize, usize)> = board.white.stones.iter().collect();
+        group.bench_function(format!("territory/{name}"), |b| {
+            b.iter(|| baseline::territory(black_box(stones.clone())))
+        });
+        group.bench_function(format!("legal_moves/{name}"), |b| {
+            b.iter(|| baseline::legal_moves(black_box(&board)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rules, baseline);
+criterion_main!(benches);
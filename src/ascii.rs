@@ -0,0 +1,209 @@
+//! Text diagrams of positions, for tests, bug reports and puzzles.
+//!
+//! The board is drawn with the top row first. Rows of squares and rows of intersections
+//! alternate, so a board of size n takes 2n - 1 lines. Squares are at even columns and hold
+//! `.` when empty, draughts `w` and `b` or watchtowers `W` and `B`. Intersections are at odd
+//! columns between them and hold stones `O` for white and `X` for black, or a space. A line
+//! like `White to move, MoveDraught` after the board sets the turn and phase, the step of the
+//! turn is the first one in that phase. Lines starting with `#` are comments.
+//!
+//! The bottom left corner of a board, with a black and two white draughts and some stones:
+//!
+//! ```text
+//! . . . . .
+//!  X X
+//! . b . w .
+//!  X   O O
+//! . . . w .
+//! ```
+
+use std::fmt;
+
+use crate::{
+    board::BoardState,
+    logic::{BoardConfig, GamePhase, Side, Turn, MAX_BOARD_SIZE},
+    rules::RuleSet,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsciiError {
+    /// Number of board lines doesn't match a supported board size.
+    Size(usize),
+    /// Unknown character or a piece where it can't be.
+    Character { line: usize, column: usize, c: char },
+    /// More than one watchtower of a side.
+    Watchtowers(Side),
+    /// Line after the board isn't a turn and phase.
+    Status(String),
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiError::Size(lines) => write!(f, "{lines} lines don't make a board"),
+            AsciiError::Character { line, column, c } => {
+                write!(f, "unexpected {c:?} on line {line}, column {column}")
+            }
+            AsciiError::Watchtowers(side) => write!(f, "{side:?} has more than one watchtower"),
+            AsciiError::Status(status) => write!(f, "can't read turn and phase from {status:?}"),
+        }
+    }
+}
+
+impl std::error::Error for AsciiError {}
+
+fn parse_status(status: &str) -> Option<(Turn, GamePhase)> {
+    let (turn, phase) = status.split_once(" to move, ")?;
+    let turn = match turn {
+        "White" => Turn::White,
+        "Black" => Turn::Black,
+        _ => return None,
+    };
+    let phase = match phase {
+        "Initialize" => GamePhase::Initialize,
+        "PlaceWatchtower" => GamePhase::PlaceWatchtower,
+        "PlaceGoPiece" => GamePhase::PlaceGoPiece,
+        "MoveDraught" => GamePhase::MoveDraught,
+        "ChooseAction" => GamePhase::ChooseAction,
+        "GameOver" => GamePhase::GameOver,
+        _ => return None,
+    };
+    Some((turn, phase))
+}
+
+impl BoardState {
+    /// Reads a position drawn as described in [`crate::ascii`], with the classic rules. Without
+    /// a status line White moves, placing watchtowers until both are on the board.
+    pub fn from_ascii(text: &str) -> Result<BoardState, AsciiError> {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with('#'))
+            .collect();
+
+        let (board_lines, status) = match lines.last() {
+            Some((_, last)) if last.contains("to move") => {
+                let status = parse_status(last).ok_or(AsciiError::Status(last.to_string()))?;
+                (&lines[..lines.len() - 1], Some(status))
+            }
+            _ => (&lines[..], None),
+        };
+
+        let size = board_lines.len().div_ceil(2);
+        if board_lines.len() % 2 == 0 || !(9..=MAX_BOARD_SIZE).contains(&size) {
+            return Err(AsciiError::Size(board_lines.len()));
+        }
+
+        let mut board = BoardState::with_rules(BoardConfig::new(size), RuleSet::default());
+        for (row, (line, text)) in board_lines.iter().enumerate() {
+            for (column, c) in text.chars().enumerate() {
+                let error = AsciiError::Character {
+                    line: line + 1,
+                    column: column + 1,
+                    c,
+                };
+                if column >= 2 * size - 1 {
+                    return Err(error);
+                }
+
+                let i = column / 2;
+                match (row % 2, column % 2, c) {
+                    (_, _, ' ') | (0, 0, '.') => {}
+                    (0, 0, _) => {
+                        let square = (i, size - 1 - row / 2);
+                        match c {
+                            'w' => board.white.draughts.insert(square),
+                            'b' => board.black.draughts.insert(square),
+                            'W' | 'B' => {
+                                let (side, army) = if c == 'W' {
+                                    (Side::White, &mut board.white)
+                                } else {
+                                    (Side::Black, &mut board.black)
+                                };
+                                if army.watchtower.is_some() {
+                                    return Err(AsciiError::Watchtowers(side));
+                                }
+                                army.watchtower = Some(square);
+                            }
+                            _ => return Err(error),
+                        }
+                    }
+                    (1, 1, _) => {
+                        let stone = (i, size - 2 - row / 2);
+                        match c {
+                            'O' => board.white.stones.insert(stone),
+                            'X' => board.black.stones.insert(stone),
+                            _ => return Err(error),
+                        }
+                    }
+                    _ => return Err(error),
+                }
+            }
+        }
+
+        (board.turn, board.phase) = status.unwrap_or_else(|| {
+            if board.white.watchtower.is_some() && board.black.watchtower.is_some() {
+                (Turn::White, board.rules.turn_structure.phase(0))
+            } else if board.white.watchtower.is_some() {
+                (Turn::Black, GamePhase::PlaceWatchtower)
+            } else {
+                (Turn::White, GamePhase::PlaceWatchtower)
+            }
+        });
+        // first step of the turn in that phase
+        board.step = (0..board.rules.turn_structure.n_steps())
+            .find(|step| board.rules.turn_structure.phase(*step) == board.phase)
+            .unwrap_or(0);
+
+        Ok(board)
+    }
+}
+
+impl fmt::Display for BoardState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.config.size;
+        for j in (0..size).rev() {
+            let mut line = String::new();
+            for i in 0..size {
+                let square = (i, j);
+                line.push(if self.white.watchtower == Some(square) {
+                    'W'
+                } else if self.black.watchtower == Some(square) {
+                    'B'
+                } else if self.white.draughts.contains(square) {
+                    'w'
+                } else if self.black.draughts.contains(square) {
+                    'b'
+                } else {
+                    '.'
+                });
+                line.push(' ');
+            }
+            writeln!(f, "{}", line.trim_end())?;
+
+            if j == 0 {
+                break;
+            }
+
+            let mut line = String::from(" ");
+            for i in 0..size - 1 {
+                let stone = (i, j - 1);
+                line.push(if self.white.stones.contains(stone) {
+                    'O'
+                } else if self.black.stones.contains(stone) {
+                    'X'
+                } else {
+                    ' '
+                });
+                line.push(' ');
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let turn = match self.turn {
+            Turn::White => "White",
+            Turn::Black => "Black",
+        };
+        write!(f, "{turn} to move, {:?}", self.phase)
+    }
+}
@@ -13,6 +13,7 @@ impl Plugin for InternalAudioPlugin {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Resource)]
 struct NeuralMusic(Handle<AudioInstance>);
 
@@ -0,0 +1,370 @@
+//! Plays bots against each other without a window.
+//!
+//!     cargo run --release --bin arena -- [OPTIONS] BOT BOT [BOT...]
+//!
+//! Every pair of bots plays `--games` games with sides swapped every game. Bots are `random`,
+//! `alphabeta[:SECONDS]`, `mcts[:PLAYOUTS]` and `engine:COMMAND` for engines in other
+//! processes. The results are printed as win, draw and loss tables with Elo estimates and every
+//! game is written to the `--out` directory as a RON record.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process, thread,
+};
+
+use bevy::{prelude::default, utils::Duration};
+use watchtower_defence::{
+    board::Move,
+    logic::{BoardConfig, GameLogic, GameResult, Side},
+    mcts::{MctsBot, MctsConfig},
+    players::{Bot, RandomBot},
+    protocol::SubprocessBot,
+    record::GameRecord,
+    rules::RuleSet,
+    search::{AlphaBetaBot, SearchConfig},
+};
+
+const USAGE: &str = "\
+usage: arena [OPTIONS] BOT BOT [BOT...]
+
+bots:
+    random               random legal moves
+    alphabeta[:SECONDS]  alpha-beta search, thinking SECONDS a move (1)
+    mcts[:PLAYOUTS]      Monte Carlo tree search, PLAYOUTS a move (1000)
+    engine:COMMAND       engine speaking the protocol of src/protocol.rs, like
+                         \"engine:target/release/engine\"
+
+options:
+    --games N       games per pair of bots (10)
+    --size N        board size, 9 to 19 (13)
+    --rules FILE    rule preset, like assets/rules/quick.rules.ron (classic)
+    --max-moves N   moves before an unfinished game is adjudicated (300)
+    --out DIR       directory for the game records (arena)";
+
+/// Time engines get for all their moves in a game.
+const ENGINE_CLOCK: Duration = Duration::from_secs(120);
+
+/// 95% of games fall within this many standard errors.
+const CONFIDENCE: f64 = 1.96;
+
+struct Options {
+    games: usize,
+    size: usize,
+    rules: RuleSet,
+    max_moves: usize,
+    out: PathBuf,
+    bots: Vec<String>,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        games: 10,
+        size: 13,
+        rules: RuleSet::default(),
+        max_moves: 300,
+        out: PathBuf::from("arena"),
+        bots: vec![],
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{arg} needs a value")))
+        };
+        let number = |value: String| {
+            value
+                .parse::<usize>()
+                .unwrap_or_else(|_| fail(&format!("{value} isn't a number")))
+        };
+        match arg.as_str() {
+            "--games" => options.games = number(value()),
+            "--size" => {
+                options.size = number(value());
+                if !BoardConfig::supports(options.size) {
+                    fail(&format!("board size {} isn't supported", options.size));
+                }
+            }
+            "--max-moves" => options.max_moves = number(value()),
+            "--out" => options.out = PathBuf::from(value()),
+            "--rules" => {
+                let path = value();
+                options.rules = RuleSet::read(Path::new(&path))
+                    .unwrap_or_else(|err| fail(&format!("{path}: {err}")));
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ if arg.starts_with("--") => fail(&format!("unknown option {arg}")),
+            _ => {
+                // fail early on a typo rather than after the first games
+                make_bot(&arg);
+                options.bots.push(arg);
+            }
+        }
+    }
+
+    if options.bots.len() < 2 {
+        fail("at least two bots are needed");
+    }
+    // without a limit two passive bots would play forever
+    if options.rules.move_limit.is_none() {
+        options.rules.move_limit = Some(options.max_moves);
+    }
+    options
+}
+
+fn make_bot(spec: &str) -> Box<dyn Bot> {
+    let (name, param) = match spec.split_once(':') {
+        Some((name, param)) => (name, Some(param)),
+        None => (spec, None),
+    };
+    let number = |default: f64| {
+        param.map_or(default, |param| {
+            param
+                .parse()
+                .unwrap_or_else(|_| fail(&format!("{param} isn't a number in {spec}")))
+        })
+    };
+
+    match name {
+        "random" => Box::new(RandomBot::new()),
+        "alphabeta" => Box::new(AlphaBetaBot::new(SearchConfig {
+            time_limit: Duration::from_secs_f64(number(1.)),
+            ..Default::default()
+        })),
+        "mcts" => Box::new(MctsBot::new(MctsConfig {
+            playouts: number(1000.) as usize,
+            ..Default::default()
+        })),
+        "engine" => {
+            let command = param.unwrap_or_else(|| fail("engine needs a command"));
+            let bot = SubprocessBot::spawn(command, ENGINE_CLOCK)
+                .and_then(|mut bot| bot.wait_ready().map(|_| bot))
+                .unwrap_or_else(|err| fail(&format!("can't start {command}: {err}")));
+            Box::new(bot)
+        }
+        _ => fail(&format!("unknown bot {spec}")),
+    }
+}
+
+/// Plays a game between fresh bots, a bot that plays an illegal move resigns.
+fn play_game(options: &Options, white: &str, black: &str) -> GameLogic {
+    let mut game = GameLogic::with_rules(BoardConfig::new(options.size), options.rules.clone());
+    let mut bots = [make_bot(white), make_bot(black)];
+
+    while game.result().is_none() {
+        let side: Side = game.board().turn.into();
+        let bot = &mut bots[usize::from(side == Side::Black)];
+        let mv = loop {
+            if let Some(mv) = bot.poll(&game) {
+                break mv;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        if let Err(err) = game.apply(mv) {
+            println!("  {} played {mv}: {err}, resigning", bot.name());
+            game.apply(Move::Resign).unwrap();
+        }
+    }
+    game
+}
+
+/// Wins, draws and losses of one bot against another.
+#[derive(Clone, Copy, Default)]
+struct Tally {
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Tally {
+    fn add(&mut self, other: Tally) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+    }
+
+    /// The same games seen from the opponent.
+    fn reversed(self) -> Tally {
+        Tally {
+            wins: self.losses,
+            draws: self.draws,
+            losses: self.wins,
+        }
+    }
+
+    fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.) / self.games() as f64
+    }
+
+    /// Elo difference to the opponents with its 95% margin, from the share of points won.
+    fn elo(&self) -> String {
+        let games = self.games() as f64;
+        let score = self.score();
+        if score <= 0. || score >= 1. {
+            return if score <= 0. { "-inf" } else { "+inf" }.into();
+        }
+
+        let elo = 400. * (score / (1. - score)).log10();
+        let variance = (self.wins as f64 * (1. - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2))
+            / games;
+        // slope of the Elo curve at the score
+        let margin = CONFIDENCE * (variance / games).sqrt() * 400.
+            / (std::f64::consts::LN_10 * score * (1. - score));
+        format!("{elo:+.0} ± {margin:.0}")
+    }
+}
+
+fn main() {
+    let options = parse_options();
+    fs::create_dir_all(&options.out).unwrap_or_else(|err| {
+        fail(&format!(
+            "can't create {}: {err}",
+            options.out.to_string_lossy()
+        ))
+    });
+
+    let n_bots = options.bots.len();
+    let mut tallies = vec![vec![Tally::default(); n_bots]; n_bots];
+    let mut n_game = 0;
+
+    for a in 0..n_bots {
+        for b in a + 1..n_bots {
+            for n in 0..options.games {
+                let (white, black) = if n.is_multiple_of(2) { (a, b) } else { (b, a) };
+                let (white_name, black_name) = (&options.bots[white], &options.bots[black]);
+                let game = play_game(&options, white_name, black_name);
+
+                n_game += 1;
+                let result = game.result().unwrap();
+                let (tally, winner) = match result {
+                    GameResult::Winner(Side::White) => (
+                        Tally {
+                            wins: 1,
+                            ..default()
+                        },
+                        format!("{white_name} wins"),
+                    ),
+                    GameResult::Winner(Side::Black) => (
+                        Tally {
+                            losses: 1,
+                            ..default()
+                        },
+                        format!("{black_name} wins"),
+                    ),
+                    GameResult::Draw => (
+                        Tally {
+                            draws: 1,
+                            ..default()
+                        },
+                        "draw".into(),
+                    ),
+                };
+                tallies[white][black].add(tally);
+                tallies[black][white].add(tally.reversed());
+                println!(
+                    "game {n_game}: {white_name} (White) vs {black_name} (Black), {winner}, {:?} after {} moves",
+                    game.ending().unwrap(),
+                    game.n_moves()
+                );
+
+                let record = GameRecord::new(&game, white_name, black_name);
+                let path = options.out.join(format!("game-{n_game:04}.ron"));
+                if let Err(err) = fs::write(&path, record.to_ron()) {
+                    eprintln!("can't write {}: {err}", path.to_string_lossy());
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------------------------
+
+    let width = options
+        .bots
+        .iter()
+        .map(|bot| bot.len())
+        .max()
+        .unwrap()
+        .max(8);
+
+    println!("\nWins-draws-losses of each row against each column");
+    print!("{:width$}", "");
+    for bot in options.bots.iter() {
+        print!("  {bot:>width$}");
+    }
+    println!();
+    for (bot, row) in options.bots.iter().zip(tallies.iter()) {
+        print!("{bot:width$}");
+        for tally in row.iter() {
+            let cell = if tally.games() == 0 {
+                "-".into()
+            } else {
+                format!("{}-{}-{}", tally.wins, tally.draws, tally.losses)
+            };
+            print!("  {cell:>width$}");
+        }
+        println!();
+    }
+
+    println!("\nElo of each row against each column");
+    print!("{:width$}", "");
+    for bot in options.bots.iter() {
+        print!("  {bot:>width$}");
+    }
+    println!();
+    for (bot, row) in options.bots.iter().zip(tallies.iter()) {
+        print!("{bot:width$}");
+        for tally in row.iter() {
+            let cell = if tally.games() == 0 {
+                "-".into()
+            } else {
+                tally.elo()
+            };
+            print!("  {cell:>width$}");
+        }
+        println!();
+    }
+
+    println!("\nAgainst the field");
+    println!(
+        "{:width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>6}  {:>10}",
+        "bot", "games", "wins", "draws", "losses", "score", "elo"
+    );
+    for (bot, row) in options.bots.iter().zip(tallies.iter()) {
+        let mut total = Tally::default();
+        for tally in row.iter() {
+            total.add(*tally);
+        }
+        if total.games() == 0 {
+            continue;
+        }
+        println!(
+            "{bot:width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>5.1}%  {:>10}",
+            total.games(),
+            total.wins,
+            total.draws,
+            total.losses,
+            total.score() * 100.,
+            total.elo()
+        );
+    }
+    println!(
+        "\n{n_game} game records written to {}",
+        options.out.to_string_lossy()
+    );
+}
@@ -0,0 +1,94 @@
+//! Reference engine for the line protocol in `src/protocol.rs`, playing with the alpha-beta
+//! search. Engines in other languages can start from the same loop:
+//!
+//!     cargo run --release --bin engine
+//!
+//! reads commands from stdin and writes replies to stdout.
+
+use std::io::{self, BufRead, Write};
+
+use bevy::utils::Duration;
+use watchtower_defence::{
+    board::Move,
+    logic::{BoardConfig, GameLogic},
+    protocol::{Command, Reply},
+    rules::RuleSet,
+    search::{search, SearchConfig},
+};
+
+/// Part of the clock spent on one move, games rarely take more moves than this.
+const MOVES_TO_GO: u32 = 40;
+
+/// Longest the engine thinks about a move, however much time there is.
+const MAX_THINK: Duration = Duration::from_secs(3);
+
+fn main() {
+    let mut rules = RuleSet::default();
+    let mut game = GameLogic::new();
+    let mut clock = MAX_THINK * MOVES_TO_GO;
+
+    let mut stdout = io::stdout();
+    let mut reply = |reply: Reply| {
+        writeln!(stdout, "{reply}").unwrap();
+        stdout.flush().unwrap();
+    };
+
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let command = match line.parse::<Command>() {
+            Ok(command) => command,
+            Err(err) => {
+                reply(Reply::Info(err.to_string()));
+                continue;
+            }
+        };
+
+        match command {
+            Command::Hello => {
+                reply(Reply::Id("Watchtower Alpha-Beta".into()));
+                reply(Reply::HelloOk);
+            }
+            Command::IsReady => reply(Reply::ReadyOk),
+            Command::Rules(new_rules) => rules = new_rules,
+            Command::Position { size, moves } => {
+                if !BoardConfig::supports(size) {
+                    reply(Reply::Info(format!("can't play on a board of size {size}")));
+                    continue;
+                }
+                // a move that can't be played leaves the previous position, not half of the new one
+                let mut position = GameLogic::with_rules(BoardConfig::new(size), rules.clone());
+                let failed = moves
+                    .into_iter()
+                    .find_map(|mv| position.apply(mv).err().map(|err| (mv, err)));
+                match failed {
+                    Some((mv, err)) => reply(Reply::Info(format!(
+                        "can't play {mv}: {err}, keeping the previous position"
+                    ))),
+                    None => game = position,
+                }
+            }
+            Command::Move(mv) => {
+                if let Err(err) = game.apply(mv) {
+                    reply(Reply::Info(format!("can't play {mv}: {err}")));
+                }
+            }
+            Command::Time(time) => clock = time,
+            Command::Go => {
+                let config = SearchConfig {
+                    time_limit: (clock / MOVES_TO_GO).min(MAX_THINK),
+                    ..Default::default()
+                };
+                let result = search(&game, &config);
+                reply(Reply::Info(format!(
+                    "depth {} score {} nodes {}",
+                    result.depth, result.score, result.nodes
+                )));
+                // nothing to play only happens in a finished game
+                reply(Reply::BestMove(result.best_move.unwrap_or(Move::Resign)));
+            }
+            Command::Quit => break,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Plays MCTS against itself and writes every position as training data.
+//!
+//!     cargo run --release --bin selfplay -- [OPTIONS]
+//!
+//! Each position is a line of JSON with planes of the pieces, the phase, the move played, the
+//! visit counts of the search and the result of the game, see `Sample` in `src/training.rs`.
+
+use std::{
+    env,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    process,
+};
+
+use watchtower_defence::{
+    logic::{BoardConfig, GameLogic},
+    mcts::MctsConfig,
+    rules::RuleSet,
+    training::SelfPlay,
+};
+
+const USAGE: &str = "\
+usage: selfplay [OPTIONS]
+
+options:
+    --games N         games to play (10)
+    --size N          board size, 9 to 19 (13)
+    --rules FILE      rule preset, like assets/rules/quick.rules.ron (classic)
+    --max-moves N     moves before an unfinished game is adjudicated (300)
+    --playouts N      MCTS playouts a move (1000)
+    --random-moves N  opening moves picked in proportion to their visits (10)
+    --out FILE        JSON lines file the samples are added to (selfplay.jsonl)";
+
+struct Options {
+    games: usize,
+    size: usize,
+    rules: RuleSet,
+    max_moves: usize,
+    playouts: usize,
+    random_moves: usize,
+    out: String,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        games: 10,
+        size: 13,
+        rules: RuleSet::default(),
+        max_moves: 300,
+        playouts: 1000,
+        random_moves: 10,
+        out: "selfplay.jsonl".into(),
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{arg} needs a value")))
+        };
+        let number = |value: String| {
+            value
+                .parse::<usize>()
+                .unwrap_or_else(|_| fail(&format!("{value} isn't a number")))
+        };
+        match arg.as_str() {
+            "--games" => options.games = number(value()),
+            "--size" => {
+                options.size = number(value());
+                if !BoardConfig::supports(options.size) {
+                    fail(&format!("board size {} isn't supported", options.size));
+                }
+            }
+            "--max-moves" => options.max_moves = number(value()),
+            "--playouts" => options.playouts = number(value()),
+            "--random-moves" => options.random_moves = number(value()),
+            "--out" => options.out = value(),
+            "--rules" => {
+                let path = value();
+                options.rules = RuleSet::read(Path::new(&path))
+                    .unwrap_or_else(|err| fail(&format!("{path}: {err}")));
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ => fail(&format!("unknown option {arg}")),
+        }
+    }
+
+    // without a limit a game could go on forever
+    if options.rules.move_limit.is_none() {
+        options.rules.move_limit = Some(options.max_moves);
+    }
+    options
+}
+
+fn main() {
+    let options = parse_options();
+    let file = File::options()
+        .create(true)
+        .append(true)
+        .open(&options.out)
+        .unwrap_or_else(|err| fail(&format!("can't open {}: {err}", options.out)));
+    let mut out = BufWriter::new(file);
+
+    let config = MctsConfig {
+        playouts: options.playouts,
+        ..Default::default()
+    };
+    let mut self_play = SelfPlay::new(config, options.random_moves);
+    let mut n_samples = 0;
+
+    for n in 1..=options.games {
+        let game = GameLogic::with_rules(BoardConfig::new(options.size), options.rules.clone());
+        let (game, samples) = self_play.play(game);
+        for sample in samples.iter() {
+            writeln!(out, "{}", sample.to_json()).unwrap();
+        }
+        out.flush().unwrap();
+
+        n_samples += samples.len();
+        println!(
+            "game {n}: {:?}, {:?} after {} moves, {} positions",
+            game.result().unwrap(),
+            game.ending().unwrap(),
+            game.n_moves(),
+            samples.len()
+        );
+    }
+    println!("{n_samples} positions added to {}", options.out);
+}
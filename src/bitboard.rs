@@ -0,0 +1,306 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not, Sub, SubAssign};
+
+use crate::logic::MAX_BOARD_SIZE;
+
+const WORDS: usize = (MAX_BOARD_SIZE * MAX_BOARD_SIZE).div_ceil(64);
+
+/// Set of points of a 19x19 board, one bit per point. Point (i, j) is bit `j * 19 + i`, so
+/// moving along `i` shifts by one bit and moving along `j` shifts by a whole row. Smaller boards
+/// use the lower left corner of it.
+///
+/// Squares and stone intersections both use it: intersections simply never touch the last row
+/// and column.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboard([u64; WORDS]);
+
+const fn set_bit(mut words: [u64; WORDS], n: usize) -> [u64; WORDS] {
+    words[n / 64] |= 1 << (n % 64);
+    words
+}
+
+const fn column_words(i: usize) -> [u64; WORDS] {
+    let mut words = [0; WORDS];
+    let mut j = 0;
+    while j < MAX_BOARD_SIZE {
+        words = set_bit(words, j * MAX_BOARD_SIZE + i);
+        j += 1;
+    }
+    words
+}
+
+const fn row_words(j: usize) -> [u64; WORDS] {
+    let mut words = [0; WORDS];
+    let mut i = 0;
+    while i < MAX_BOARD_SIZE {
+        words = set_bit(words, j * MAX_BOARD_SIZE + i);
+        i += 1;
+    }
+    words
+}
+
+const fn full_words() -> [u64; WORDS] {
+    let mut words = [0; WORDS];
+    let mut n = 0;
+    while n < MAX_BOARD_SIZE * MAX_BOARD_SIZE {
+        words = set_bit(words, n);
+        n += 1;
+    }
+    words
+}
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard([0; WORDS]);
+    pub const FULL: Bitboard = Bitboard(full_words());
+
+    const FIRST_COLUMN: Bitboard = Bitboard(column_words(0));
+    const LAST_COLUMN: Bitboard = Bitboard(column_words(MAX_BOARD_SIZE - 1));
+
+    pub fn from_point((i, j): (usize, usize)) -> Self {
+        let mut board = Bitboard::EMPTY;
+        board.insert((i, j));
+        board
+    }
+
+    pub fn column(i: usize) -> Self {
+        Bitboard(column_words(i))
+    }
+
+    pub fn row(j: usize) -> Self {
+        Bitboard(row_words(j))
+    }
+
+    /// Points with `i < width` and `j < height`.
+    pub fn rect(width: usize, height: usize) -> Self {
+        let mut board = Bitboard::EMPTY;
+        for j in 0..height.min(MAX_BOARD_SIZE) {
+            for i in 0..width.min(MAX_BOARD_SIZE) {
+                board.insert((i, j));
+            }
+        }
+        board
+    }
+
+    pub fn contains(&self, (i, j): (usize, usize)) -> bool {
+        if i >= MAX_BOARD_SIZE || j >= MAX_BOARD_SIZE {
+            return false;
+        }
+        let n = j * MAX_BOARD_SIZE + i;
+        self.0[n / 64] & (1 << (n % 64)) != 0
+    }
+
+    pub fn insert(&mut self, (i, j): (usize, usize)) {
+        debug_assert!(i < MAX_BOARD_SIZE && j < MAX_BOARD_SIZE);
+        let n = j * MAX_BOARD_SIZE + i;
+        self.0[n / 64] |= 1 << (n % 64);
+    }
+
+    pub fn remove(&mut self, (i, j): (usize, usize)) {
+        if i >= MAX_BOARD_SIZE || j >= MAX_BOARD_SIZE {
+            return;
+        }
+        let n = j * MAX_BOARD_SIZE + i;
+        self.0[n / 64] &= !(1 << (n % 64));
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// Point with the lowest index, that is the lowest `j` and then the lowest `i`.
+    pub fn first(&self) -> Option<(usize, usize)> {
+        self.0.iter().enumerate().find_map(|(w, word)| {
+            if *word == 0 {
+                return None;
+            }
+            let n = w * 64 + word.trailing_zeros() as usize;
+            Some((n % MAX_BOARD_SIZE, n / MAX_BOARD_SIZE))
+        })
+    }
+
+    pub fn iter(&self) -> BitboardIter {
+        BitboardIter(*self)
+    }
+
+    fn shl(&self, n: usize) -> Self {
+        let (words, bits) = (n / 64, n % 64);
+        let mut result = [0; WORDS];
+        for w in (words..WORDS).rev() {
+            result[w] = self.0[w - words] << bits;
+            if bits != 0 && w > words {
+                result[w] |= self.0[w - words - 1] >> (64 - bits);
+            }
+        }
+        Bitboard(result) & Bitboard::FULL
+    }
+
+    fn shr(&self, n: usize) -> Self {
+        let (words, bits) = (n / 64, n % 64);
+        let mut result = [0; WORDS];
+        for (w, word) in result.iter_mut().enumerate().take(WORDS - words) {
+            *word = self.0[w + words] >> bits;
+            if bits != 0 && w + words + 1 < WORDS {
+                *word |= self.0[w + words + 1] << (64 - bits);
+            }
+        }
+        Bitboard(result)
+    }
+
+    /// Moves every point by (di, dj). Points pushed off the board are dropped.
+    pub fn shift(&self, di: i32, dj: i32) -> Self {
+        let mut board = *self;
+
+        for _ in 0..di.unsigned_abs() {
+            board = if di > 0 {
+                (board & !Bitboard::LAST_COLUMN).shl(1)
+            } else {
+                (board & !Bitboard::FIRST_COLUMN).shr(1)
+            };
+        }
+
+        if dj > 0 {
+            board = board.shl(dj as usize * MAX_BOARD_SIZE);
+        } else if dj < 0 {
+            board = board.shr(dj.unsigned_abs() as usize * MAX_BOARD_SIZE);
+        }
+
+        board
+    }
+
+    /// Points next to the set horizontally or vertically.
+    pub fn neighbours(&self) -> Self {
+        self.shift(1, 0) | self.shift(-1, 0) | self.shift(0, 1) | self.shift(0, -1)
+    }
+
+    /// Points next to the set in any of eight directions.
+    pub fn king_neighbours(&self) -> Self {
+        let horizontal = *self | self.shift(1, 0) | self.shift(-1, 0);
+        horizontal | horizontal.shift(0, 1) | horizontal.shift(0, -1)
+    }
+
+    /// Everything in `mask` reachable from the set with horizontal and vertical steps.
+    pub fn flood_fill(&self, mask: Bitboard) -> Self {
+        let mut filled = *self & mask;
+        loop {
+            let expanded = (filled | filled.neighbours()) & mask;
+            if expanded == filled {
+                return filled;
+            }
+            filled = expanded;
+        }
+    }
+
+    /// Everything in `mask` reachable from the set with steps in any of eight directions.
+    pub fn king_flood_fill(&self, mask: Bitboard) -> Self {
+        let mut filled = *self & mask;
+        loop {
+            let expanded = filled.king_neighbours() & mask;
+            if expanded == filled {
+                return filled;
+            }
+            filled = expanded;
+        }
+    }
+}
+
+pub struct BitboardIter(Bitboard);
+
+impl Iterator for BitboardIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.0.first()?;
+        self.0.remove(point);
+        Some(point)
+    }
+}
+
+impl FromIterator<(usize, usize)> for Bitboard {
+    fn from_iter<T: IntoIterator<Item = (usize, usize)>>(iter: T) -> Self {
+        let mut board = Bitboard::EMPTY;
+        for point in iter {
+            board.insert(point);
+        }
+        board
+    }
+}
+
+impl fmt::Debug for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+
+    fn bitand(mut self, rhs: Bitboard) -> Bitboard {
+        self &= rhs;
+        self
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Bitboard) {
+        for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *word &= rhs;
+        }
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+
+    fn bitor(mut self, rhs: Bitboard) -> Bitboard {
+        self |= rhs;
+        self
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Bitboard) {
+        for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *word |= rhs;
+        }
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+
+    fn bitxor(mut self, rhs: Bitboard) -> Bitboard {
+        for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *word ^= rhs;
+        }
+        self
+    }
+}
+
+impl Sub for Bitboard {
+    type Output = Bitboard;
+
+    fn sub(self, rhs: Bitboard) -> Bitboard {
+        self & !rhs
+    }
+}
+
+impl SubAssign for Bitboard {
+    fn sub_assign(&mut self, rhs: Bitboard) {
+        *self &= !rhs;
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(mut self) -> Bitboard {
+        for word in self.0.iter_mut() {
+            *word = !*word;
+        }
+        self & Bitboard::FULL
+    }
+}
@@ -0,0 +1,765 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitboard::Bitboard,
+    logic::*,
+    rules::{ChainedTakeovers, RuleSet},
+    stats::*,
+    territory::{enclosure, Enclosure},
+    turns::Action,
+};
+
+/// Pieces one side has on the board.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Army {
+    pub draughts: Bitboard,
+    pub stones: Bitboard,
+    pub watchtower: Option<(usize, usize)>,
+    /// Enemy draughts taken over so far.
+    pub captured_draughts: usize,
+}
+
+/// The whole position together with the rules that change it. Doesn't know anything about
+/// entities, so games can be played and analysed without a window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardState {
+    pub white: Army,
+    pub black: Army,
+    pub turn: Turn,
+    pub phase: GamePhase,
+    pub config: BoardConfig,
+    pub rules: RuleSet,
+    /// Step of the turn structure of the rules being played.
+    pub step: usize,
+    /// Draught in the middle of a chain of takeovers. Until the chain ends it's the only piece
+    /// that moves, and it can only take over.
+    pub chain: Option<(usize, usize)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Move {
+    /// Put the watchtower on square (i, j), surrounded by its draughts and stones.
+    PlaceWatchtower { i: usize, j: usize },
+    /// Put a stone on intersection (i, j).
+    PlaceStone { i: usize, j: usize },
+    /// Move a draught to a free neighbouring square.
+    StepDraught {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// Jump over a neighbouring enemy draught, which then joins our side.
+    TakeOverDraught {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// Blow up the draught on square (i, j) together with everything around it.
+    Nuke { i: usize, j: usize },
+    /// Skip the step of the turn, or stop an optional chain of takeovers. Watchtowers can only
+    /// be skipped when they don't fit anywhere.
+    Pass,
+    /// Give up the game.
+    Resign,
+    /// Propose a draw. The opponent accepts it on their turn or declines it by playing on.
+    OfferDraw,
+    /// Accept the draw the opponent offered.
+    AcceptDraw,
+}
+
+impl Move {
+    /// Square a draught move ends on.
+    pub fn destination(&self) -> Option<(usize, usize)> {
+        match self {
+            Move::StepDraught { to, .. } | Move::TakeOverDraught { to, .. } => Some(*to),
+            _ => None,
+        }
+    }
+
+    /// Whether the move is played on the board. Resigning and draws are settled between the
+    /// players by [`GameLogic`].
+    pub fn is_board_move(&self) -> bool {
+        !matches!(self, Move::Resign | Move::OfferDraw | Move::AcceptDraw)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    GameOver,
+    WrongPhase,
+    OutOfBoard,
+    WatchtowerAlreadyPlaced,
+    TooCloseToWatchtower,
+    WatchtowerBlocked,
+    NoRoomForEnemyWatchtower,
+    NoDraught,
+    IllegalDraughtMove,
+    ChainInProgress,
+    Occupied,
+    EnemyTerritory,
+    SelfCapture,
+    CannotPass,
+    RepeatedPosition,
+    NotABoardMove,
+    NoDrawOffer,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            MoveError::GameOver => "the game is over",
+            MoveError::WrongPhase => "move doesn't match the game phase",
+            MoveError::OutOfBoard => "move is out of the board",
+            MoveError::WatchtowerAlreadyPlaced => "watchtower is already placed",
+            MoveError::TooCloseToWatchtower => "too close to the enemy watchtower",
+            MoveError::WatchtowerBlocked => "watchtower pieces would land on enemy ones",
+            MoveError::NoRoomForEnemyWatchtower => "enemy watchtower would have nowhere to go",
+            MoveError::NoDraught => "no draught of the side to move there",
+            MoveError::IllegalDraughtMove => "draught can't move there",
+            MoveError::ChainInProgress => "only the draught taking over in a chain can move",
+            MoveError::Occupied => "intersection is already taken",
+            MoveError::EnemyTerritory => "intersection is enclosed by the enemy",
+            MoveError::SelfCapture => "stone would stay enclosed by the enemy",
+            MoveError::CannotPass => "passing isn't allowed now",
+            MoveError::RepeatedPosition => "move repeats an earlier position",
+            MoveError::NotABoardMove => "resigning and draws aren't played on the board",
+            MoveError::NoDrawOffer => "there is no draw offer to accept",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// What a move changed on the board, so the ECS can render it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Side that made the move.
+    pub side: Side,
+    pub watchtower: Option<(usize, usize)>,
+    pub placed_draughts: Vec<(usize, usize)>,
+    pub placed_stones: Vec<(usize, usize)>,
+    pub moved_draught: Option<((usize, usize), (usize, usize))>,
+    /// Enemy draughts that now belong to `side`.
+    pub taken_over_draughts: Vec<(usize, usize)>,
+    pub removed_draughts: Vec<(Side, (usize, usize))>,
+    pub removed_stones: Vec<(Side, (usize, usize))>,
+    pub removed_watchtowers: Vec<Side>,
+    /// Territory of `side` after a stone placement.
+    pub territory: Vec<(usize, usize)>,
+}
+
+/// Area a nuke clears, clipped to the board.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Blast {
+    /// Squares whose draughts and watchtowers are destroyed.
+    pub squares: Bitboard,
+    /// Corners of those squares, stones on them are destroyed.
+    pub intersections: Bitboard,
+}
+
+impl Outcome {
+    pub fn new(side: Side) -> Self {
+        Outcome {
+            side,
+            watchtower: None,
+            placed_draughts: vec![],
+            placed_stones: vec![],
+            moved_draught: None,
+            taken_over_draughts: vec![],
+            removed_draughts: vec![],
+            removed_stones: vec![],
+            removed_watchtowers: vec![],
+            territory: vec![],
+        }
+    }
+}
+
+impl Default for BoardState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardState {
+    pub fn new() -> Self {
+        Self::with_rules(BoardConfig::default(), RuleSet::default())
+    }
+
+    pub fn with_rules(config: BoardConfig, rules: RuleSet) -> Self {
+        BoardState {
+            white: Army::default(),
+            black: Army::default(),
+            turn: Turn::default(),
+            phase: GamePhase::PlaceWatchtower,
+            config,
+            rules,
+            step: 0,
+            chain: None,
+        }
+    }
+
+    pub fn army(&self, side: Side) -> &Army {
+        match side {
+            Side::Black => &self.black,
+            Side::White => &self.white,
+        }
+    }
+
+    fn army_mut(&mut self, side: Side) -> &mut Army {
+        match side {
+            Side::Black => &mut self.black,
+            Side::White => &mut self.white,
+        }
+    }
+
+    /// Side whose watchtower is the only one left standing.
+    pub fn winner(&self) -> Option<Side> {
+        if self.phase == GamePhase::Initialize || self.phase == GamePhase::PlaceWatchtower {
+            return None;
+        }
+
+        match (self.white.watchtower, self.black.watchtower) {
+            (Some(_), None) => Some(Side::White),
+            (None, Some(_)) => Some(Side::Black),
+            _ => None,
+        }
+    }
+
+    /// Whether both watchtowers were nuked, which ends the game without a winner by towers.
+    pub fn watchtowers_destroyed(&self) -> bool {
+        if self.phase == GamePhase::Initialize || self.phase == GamePhase::PlaceWatchtower {
+            return false;
+        }
+
+        self.white.watchtower.is_none() && self.black.watchtower.is_none()
+    }
+
+    /// Area enclosed by the stones of `turn`.
+    pub fn enclosure(&self, turn: Turn) -> Enclosure {
+        enclosure(self.config, &self.rules, self.army(turn.into()).stones)
+    }
+
+    pub fn territory(&self, turn: Turn) -> Bitboard {
+        self.enclosure(turn).territory
+    }
+
+    pub fn side_stats(&self, side: Side) -> SideStats {
+        let army = self.army(side);
+        SideStats {
+            territory: self.territory(Turn::from(side)).count(),
+            draughts: army.draughts.count(),
+            captured_draughts: army.captured_draughts,
+            watchtower: army.watchtower.is_some(),
+        }
+    }
+
+    /// Both sides' positions valued with the score weights of the rules.
+    pub fn score(&self) -> Score {
+        Score {
+            white: self
+                .side_stats(Side::White)
+                .score(&self.rules.score_weights),
+            black: self
+                .side_stats(Side::Black)
+                .score(&self.rules.score_weights),
+        }
+    }
+
+    /// Steps and takeovers available to the draught of the side to move on square `from`.
+    pub fn draught_moves(&self, from: (usize, usize)) -> Vec<Move> {
+        self.draught_moves_within(from, self.territory(self.turn.next()))
+    }
+
+    fn draught_moves_within(&self, from: (usize, usize), enemy_territory: Bitboard) -> Vec<Move> {
+        let side: Side = self.turn.into();
+        let (army, enemy) = (self.army(side), self.army(side.opposite()));
+        if !army.draughts.contains(from) || self.chain.is_some_and(|chain| chain != from) {
+            return vec![];
+        }
+
+        let moves = legal_draught_moves(
+            self.config,
+            from,
+            army.draughts,
+            enemy.draughts,
+            enemy_territory,
+        )
+        .into_iter()
+        .map(|(to, movetype)| match movetype {
+            CheckersMoveType::DraughtTakeOver => Move::TakeOverDraught { from, to },
+            _ => Move::StepDraught { from, to },
+        });
+
+        if self.chain.is_some() {
+            moves
+                .filter(|mv| matches!(mv, Move::TakeOverDraught { .. }))
+                .collect()
+        } else {
+            moves.collect()
+        }
+    }
+
+    /// Squares where the side to move may put its watchtower.
+    pub fn watchtower_moves(&self) -> Vec<Move> {
+        if self.phase != GamePhase::PlaceWatchtower {
+            return vec![];
+        }
+
+        self.legal_tower_placements(self.turn.into())
+            .iter()
+            .map(|(i, j)| Move::PlaceWatchtower { i, j })
+            .collect()
+    }
+
+    /// Squares where `side` may put its watchtower, whatever the phase of the game.
+    pub fn legal_tower_placements(&self, side: Side) -> Bitboard {
+        let enemy_enclosure = self.enclosure(side.opposite().into());
+        self.config
+            .squares()
+            .iter()
+            .filter(|center| {
+                self.check_watchtower_within(side, *center, &enemy_enclosure)
+                    .is_ok()
+            })
+            .collect()
+    }
+
+    /// Intersections where the side to move may put a stone.
+    pub fn stone_moves(&self) -> Vec<Move> {
+        if !self.allows(Action::PlaceStone) || self.chain.is_some() {
+            return vec![];
+        }
+
+        self.legal_stone_placements(self.turn)
+            .iter()
+            .map(|(i, j)| Move::PlaceStone { i, j })
+            .collect()
+    }
+
+    /// Intersections where `turn` may put a stone, whatever the phase of the game.
+    pub fn legal_stone_placements(&self, turn: Turn) -> Bitboard {
+        let free = self.config.intersections() - self.white.stones - self.black.stones;
+        let enemy_enclosed = self.enclosure(turn.next()).intersections;
+
+        // enclosed intersections are only legal when the stone breaks the enclosure
+        let mut placements = free - enemy_enclosed;
+        for stone in (free & enemy_enclosed).iter() {
+            if self.check_stone(turn, stone).is_ok() {
+                placements.insert(stone);
+            }
+        }
+        placements
+    }
+
+    /// A stone can't go on a taken intersection or inside of an area the enemy encloses, unless
+    /// the stones it captures open the area up. Enemy enclosures only shrink when we play, so a
+    /// stone that ends up enclosed was enclosed before.
+    fn check_stone(&self, turn: Turn, stone: (usize, usize)) -> Result<(), MoveError> {
+        if !self.config.intersections().contains(stone) {
+            return Err(MoveError::OutOfBoard);
+        }
+
+        if self.white.stones.contains(stone) || self.black.stones.contains(stone) {
+            return Err(MoveError::Occupied);
+        }
+
+        let side: Side = turn.into();
+        let enemy = self.army(side.opposite());
+        if !self.enclosure(turn.next()).intersections.contains(stone) {
+            return Ok(());
+        }
+
+        let mut stones = self.army(side).stones;
+        stones.insert(stone);
+        let captured = enemy.stones & enclosure(self.config, &self.rules, stones).intersections;
+        if captured.is_empty() {
+            return Err(MoveError::EnemyTerritory);
+        }
+
+        let enemy_enclosed =
+            enclosure(self.config, &self.rules, enemy.stones - captured).intersections;
+        if enemy_enclosed.contains(stone) {
+            return Err(MoveError::SelfCapture);
+        }
+
+        Ok(())
+    }
+
+    /// Every legal move of the side to move. Any step of a turn can be passed, except in a
+    /// compulsory chain of takeovers.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        match self.phase {
+            GamePhase::PlaceWatchtower => {
+                let mut moves = self.watchtower_moves();
+                if moves.is_empty() {
+                    moves.push(Move::Pass);
+                }
+                moves
+            }
+            GamePhase::PlaceGoPiece | GamePhase::MoveDraught | GamePhase::ChooseAction => {
+                let mut moves = self.draught_turn_moves();
+                moves.extend(self.stone_moves());
+                if self.chain.is_none()
+                    || self.rules.chained_takeovers == ChainedTakeovers::Optional
+                {
+                    moves.push(Move::Pass);
+                }
+                moves
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Draught moves and nukes of the side to move, if the step of the turn allows them.
+    fn draught_turn_moves(&self) -> Vec<Move> {
+        if !self.allows(Action::MoveDraught) {
+            return vec![];
+        }
+
+        let draughts = self.army(self.turn.into()).draughts;
+        let enemy_territory = self.territory(self.turn.next());
+        let mut moves: Vec<Move> = draughts
+            .iter()
+            .flat_map(|draught| self.draught_moves_within(draught, enemy_territory))
+            .collect();
+        if self.chain.is_none() {
+            moves.extend(draughts.iter().map(|(i, j)| Move::Nuke { i, j }));
+        }
+        moves
+    }
+
+    /// Plays `mv` for the side to move. The position is left untouched when the move is illegal.
+    pub fn apply(&mut self, mv: Move) -> Result<Outcome, MoveError> {
+        if self.phase == GamePhase::GameOver {
+            return Err(MoveError::GameOver);
+        }
+
+        let mut outcome = Outcome::new(self.turn.into());
+        match mv {
+            Move::PlaceWatchtower { i, j } => self.place_watchtower((i, j), &mut outcome)?,
+            Move::PlaceStone { i, j } => self.place_stone((i, j), &mut outcome)?,
+            Move::StepDraught { .. } | Move::TakeOverDraught { .. } => {
+                self.move_draught(mv, &mut outcome)?
+            }
+            Move::Nuke { i, j } => self.nuke((i, j), &mut outcome)?,
+            Move::Pass => {
+                if !self.legal_moves().contains(&Move::Pass) {
+                    return Err(MoveError::CannotPass);
+                }
+            }
+            Move::Resign | Move::OfferDraw | Move::AcceptDraw => {
+                return Err(MoveError::NotABoardMove)
+            }
+        }
+
+        // a draught that can take over again keeps going within the same turn
+        self.chain = match mv {
+            Move::TakeOverDraught { to, .. } => Some(to),
+            _ => None,
+        };
+        if self
+            .chain
+            .is_some_and(|chain| self.draught_moves(chain).is_empty())
+        {
+            self.chain = None;
+        }
+
+        if self.chain.is_none() {
+            self.advance();
+        }
+        if self.winner().is_some() || self.watchtowers_destroyed() {
+            self.phase = GamePhase::GameOver;
+        }
+
+        Ok(outcome)
+    }
+
+    fn expect_phase(&self, phase: GamePhase) -> Result<(), MoveError> {
+        if self.phase != phase {
+            return Err(MoveError::WrongPhase);
+        }
+        Ok(())
+    }
+
+    /// Whether the step of the turn being played allows `action`.
+    pub fn allows(&self, action: Action) -> bool {
+        match self.phase {
+            GamePhase::PlaceGoPiece | GamePhase::MoveDraught | GamePhase::ChooseAction => {
+                self.rules.turn_structure.allows(self.step, action)
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_action(&self, action: Action) -> Result<(), MoveError> {
+        if !self.allows(action) {
+            return Err(MoveError::WrongPhase);
+        }
+        Ok(())
+    }
+
+    /// Moves on to the next step of the turn, or the next turn after its last step.
+    fn advance(&mut self) {
+        let turn_structure = &self.rules.turn_structure;
+        match self.phase {
+            GamePhase::PlaceWatchtower => {
+                if self.turn == Turn::Black {
+                    self.step = 0;
+                    self.phase = turn_structure.phase(0);
+                }
+                self.turn = self.turn.next();
+            }
+            GamePhase::PlaceGoPiece | GamePhase::MoveDraught | GamePhase::ChooseAction => {
+                self.step += 1;
+                if self.step >= turn_structure.n_steps() {
+                    self.step = 0;
+                    self.turn = self.turn.next();
+                }
+                self.phase = turn_structure.phase(self.step);
+            }
+            _ => {}
+        }
+    }
+
+    /// Draughts and stones that come with a watchtower on `center`, none if some of them don't
+    /// fit on the board.
+    fn watchtower_pieces(&self, center: (usize, usize)) -> Option<(Bitboard, Bitboard)> {
+        let pieces = |offsets: &[(i32, i32)], area: Bitboard| -> Option<Bitboard> {
+            let mut pieces = Bitboard::EMPTY;
+            for (di, dj) in offsets {
+                let (i, j) = (center.0 as i32 + di, center.1 as i32 + dj);
+                if i < 0 || j < 0 || !area.contains((i as usize, j as usize)) {
+                    return None;
+                }
+                pieces.insert((i as usize, j as usize));
+            }
+            Some(pieces)
+        };
+
+        Some((
+            pieces(&self.rules.watchtower_draughts, self.config.squares())?,
+            pieces(&self.rules.watchtower_stones, self.config.intersections())?,
+        ))
+    }
+
+    /// The watchtower keeps off the edge and away from the enemy one, and its draughts and
+    /// stones have to fit on the board without landing on enemy pieces or territory.
+    fn check_watchtower(&self, side: Side, center: (usize, usize)) -> Result<(), MoveError> {
+        self.check_watchtower_within(side, center, &self.enclosure(side.opposite().into()))
+    }
+
+    /// Same as `check_watchtower`, with the area the enemy encloses worked out already.
+    fn check_watchtower_within(
+        &self,
+        side: Side,
+        center: (usize, usize),
+        enemy_enclosure: &Enclosure,
+    ) -> Result<(), MoveError> {
+        if self.army(side).watchtower.is_some() {
+            return Err(MoveError::WatchtowerAlreadyPlaced);
+        }
+
+        // don't allow placing the watchtower on the edge
+        if !self.config.fits_watchtower(center) {
+            return Err(MoveError::OutOfBoard);
+        }
+
+        let far_enough = |a: (usize, usize), b: (usize, usize)| {
+            a.0.abs_diff(b.0).pow(2) + a.1.abs_diff(b.1).pow(2)
+                >= self.rules.min_watchtower_distance.pow(2)
+        };
+
+        let enemy = self.army(side.opposite());
+        match enemy.watchtower {
+            Some(enemy_watchtower) if !far_enough(center, enemy_watchtower) => {
+                return Err(MoveError::TooCloseToWatchtower);
+            }
+            Some(_) => {}
+            // the first watchtower has to leave the second one somewhere to go
+            None => {
+                let squares = self.config.squares();
+                if !squares
+                    .iter()
+                    .any(|other| self.config.fits_watchtower(other) && far_enough(center, other))
+                {
+                    return Err(MoveError::NoRoomForEnemyWatchtower);
+                }
+            }
+        }
+
+        let Some((mut draughts, stones)) = self.watchtower_pieces(center) else {
+            return Err(MoveError::OutOfBoard);
+        };
+        draughts.insert(center);
+
+        if !(draughts & (enemy.draughts | enemy_enclosure.territory)).is_empty()
+            || !(stones & (enemy.stones | enemy_enclosure.intersections)).is_empty()
+        {
+            return Err(MoveError::WatchtowerBlocked);
+        }
+
+        Ok(())
+    }
+
+    fn place_watchtower(
+        &mut self,
+        center: (usize, usize),
+        outcome: &mut Outcome,
+    ) -> Result<(), MoveError> {
+        self.expect_phase(GamePhase::PlaceWatchtower)?;
+        self.check_watchtower(outcome.side, center)?;
+
+        let Some((draughts, stones)) = self.watchtower_pieces(center) else {
+            return Err(MoveError::OutOfBoard);
+        };
+
+        outcome.watchtower = Some(center);
+        outcome.placed_draughts = draughts.iter().collect();
+        outcome.placed_stones = stones.iter().collect();
+
+        let army = self.army_mut(outcome.side);
+        army.watchtower = Some(center);
+        army.draughts |= draughts;
+        army.stones |= stones;
+
+        Ok(())
+    }
+
+    fn place_stone(
+        &mut self,
+        stone: (usize, usize),
+        outcome: &mut Outcome,
+    ) -> Result<(), MoveError> {
+        self.expect_action(Action::PlaceStone)?;
+        if self.chain.is_some() {
+            return Err(MoveError::ChainInProgress);
+        }
+        self.check_stone(self.turn, stone)?;
+
+        let side = outcome.side;
+        self.army_mut(side).stones.insert(stone);
+        outcome.placed_stones.push(stone);
+
+        let Enclosure {
+            territory,
+            intersections,
+        } = self.enclosure(self.turn);
+
+        // remove enemy stones, take over enemy draughts
+        let enemy = self.army_mut(side.opposite());
+        let removed_stones = enemy.stones & intersections;
+        let taken_over_draughts = enemy.draughts & territory;
+        enemy.stones -= removed_stones;
+        enemy.draughts -= taken_over_draughts;
+        let army = self.army_mut(side);
+        army.draughts |= taken_over_draughts;
+        army.captured_draughts += taken_over_draughts.count();
+
+        outcome.removed_stones = removed_stones
+            .iter()
+            .map(|stone| (side.opposite(), stone))
+            .collect();
+        outcome.taken_over_draughts = taken_over_draughts.iter().collect();
+        outcome.territory = territory.iter().collect();
+
+        Ok(())
+    }
+
+    fn move_draught(&mut self, mv: Move, outcome: &mut Outcome) -> Result<(), MoveError> {
+        self.expect_action(Action::MoveDraught)?;
+
+        let side = outcome.side;
+        let (from, to) = match mv {
+            Move::StepDraught { from, to } | Move::TakeOverDraught { from, to } => (from, to),
+            _ => return Err(MoveError::IllegalDraughtMove),
+        };
+
+        if !self.army(side).draughts.contains(from) {
+            return Err(MoveError::NoDraught);
+        }
+
+        if self.chain.is_some_and(|chain| chain != from) {
+            return Err(MoveError::ChainInProgress);
+        }
+
+        if !self.draught_moves(from).contains(&mv) {
+            return Err(MoveError::IllegalDraughtMove);
+        }
+
+        if let Move::TakeOverDraught { .. } = mv {
+            let takeover = ((from.0 + to.0) / 2, (from.1 + to.1) / 2);
+            self.army_mut(side.opposite()).draughts.remove(takeover);
+            let army = self.army_mut(side);
+            army.draughts.insert(takeover);
+            army.captured_draughts += 1;
+            outcome.taken_over_draughts.push(takeover);
+        }
+
+        let draughts = &mut self.army_mut(side).draughts;
+        draughts.remove(from);
+        draughts.insert(to);
+        outcome.moved_draught = Some((from, to));
+
+        Ok(())
+    }
+
+    /// Area cleared by nuking the draught on square `center`: every square within
+    /// `nuke_radius` steps in any direction and the intersections on their corners.
+    pub fn blast(&self, center: (usize, usize)) -> Blast {
+        let mut squares = Bitboard::from_point(center);
+        for _ in 0..self.rules.nuke_radius {
+            squares = squares.king_neighbours();
+        }
+        squares &= self.config.squares();
+
+        // stone (i, j) is the top right corner of square (i, j)
+        let corners = squares | squares.shift(-1, 0) | squares.shift(0, -1) | squares.shift(-1, -1);
+
+        Blast {
+            squares,
+            intersections: corners & self.config.intersections(),
+        }
+    }
+
+    /// Destroys everything in the blast, pieces of the side that nukes included: draughts,
+    /// stones and watchtowers of both sides, and the nuked draught itself.
+    fn nuke(&mut self, center: (usize, usize), outcome: &mut Outcome) -> Result<(), MoveError> {
+        self.expect_action(Action::MoveDraught)?;
+
+        if self.chain.is_some() {
+            return Err(MoveError::ChainInProgress);
+        }
+
+        if !self.army(outcome.side).draughts.contains(center) {
+            return Err(MoveError::NoDraught);
+        }
+
+        let blast = self.blast(center);
+
+        for side in [Side::White, Side::Black] {
+            let army = self.army_mut(side);
+
+            let removed_draughts = army.draughts & blast.squares;
+            let removed_stones = army.stones & blast.intersections;
+            army.draughts -= removed_draughts;
+            army.stones -= removed_stones;
+            outcome
+                .removed_draughts
+                .extend(removed_draughts.iter().map(|draught| (side, draught)));
+            outcome
+                .removed_stones
+                .extend(removed_stones.iter().map(|stone| (side, stone)));
+
+            if army
+                .watchtower
+                .is_some_and(|watchtower| blast.squares.contains(watchtower))
+            {
+                army.watchtower = None;
+                outcome.removed_watchtowers.push(side);
+            }
+        }
+
+        Ok(())
+    }
+}
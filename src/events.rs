@@ -2,6 +2,10 @@ use bevy::prelude::*;
 
 use bevy_mod_picking::prelude::*;
 
+use crate::{
+    board::{Move, Outcome},
+    logic::{GamePhase, Turn},
+};
 
 // ------
 // Events
@@ -44,10 +48,11 @@ impl From<ListenerInput<Pointer<Click>>> for EventClickCircle {
 }
 
 #[derive(Event)]
-pub struct EventClickWatchtower(pub Entity);
+pub struct EventMovePlayed(pub Move, pub Outcome);
 
-impl From<ListenerInput<Pointer<Click>>> for EventClickWatchtower {
-    fn from(event: ListenerInput<Pointer<Click>>) -> Self {
-        EventClickWatchtower(event.target)
-    }
+/// A step of a turn starts, the board is ready for the next action.
+#[derive(Event)]
+pub struct EventStepStarted {
+    pub phase: GamePhase,
+    pub turn: Turn,
 }
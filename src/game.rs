@@ -1,6 +1,10 @@
 use std::time::Duration;
 
-use crate::{events::*, loading::*, logic::*, ui::*, GameState};
+use crate::{
+    bitboard::Bitboard, board::Move, events::*, loading::*, logic::*, mcts::MctsConfig,
+    players::Players, rules::RuleSet, save::*, search::SearchConfig, turns::Action, ui::*,
+    GameState,
+};
 use bevy::prelude::*;
 
 use bevy_mod_picking::prelude::*;
@@ -29,6 +33,12 @@ struct Square {
     pub j: usize,
 }
 
+#[derive(Component)]
+struct ChainSquare;
+
+#[derive(Component)]
+struct TowerPlacementSquare;
+
 #[derive(Component)]
 struct Circle {
     pub i: usize,
@@ -37,208 +47,436 @@ struct Circle {
 
 #[derive(Resource)]
 pub struct SelectedDraught {
-    pub n: Option<i8>,
+    pub n: Option<usize>,
 }
 
+/// Time a bot or remote move waits after the previous move, so it can be followed on screen.
+const CONTROLLER_MOVE_DELAY: Duration = Duration::from_millis(600);
+
 // -------
 // Systems
 // -------
 
-fn spawn_go_piece(
+/// Whether the side to move is played with the pointer on this machine.
+pub fn human_to_move(players: Res<Players>, turn: Res<Turn>) -> bool {
+    players.side((*turn).into()).is_human()
+}
+
+/// Lets the side to move do what the step of the turn allows: place a stone on the shown
+/// intersections, move one of its draughts, or both. Nothing can be clicked when the side
+/// isn't played from here.
+fn prepare_step(
     mut commands: Commands,
-    q_squares: Query<(Entity, &Square)>,
+    mut er_step_started: EventReader<EventStepStarted>,
     mut q_circles: Query<(Entity, &mut Visibility, &Circle)>,
+    q_draughts: Query<(Entity, &Draught)>,
+    game_logic: Res<GameLogic>,
+    players: Res<Players>,
 ) {
-    for (entity, _) in q_squares.iter() {
-        commands.entity(entity).remove::<PickableBundle>();
+    let Some(step) = er_step_started.read().last() else {
+        return;
+    };
+    let board = game_logic.board();
+    let side: Side = step.turn.into();
+    let human = players.side(side).is_human();
+
+    // only legal intersections are shown and can be clicked
+    let placements = if human && board.allows(Action::PlaceStone) {
+        board.legal_stone_placements(step.turn)
+    } else {
+        Bitboard::EMPTY
+    };
+    for (entity, mut visibility, circle) in q_circles.iter_mut() {
+        if placements.contains((circle.i, circle.j)) {
+            *visibility = Visibility::Visible;
+            commands.entity(entity).insert(PickableBundle::default());
+        } else {
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).remove::<PickableBundle>();
+        }
     }
 
-    for (_, mut visibility, _) in q_circles.iter_mut() {
-        *visibility = Visibility::Visible;
+    let can_move = human && board.allows(Action::MoveDraught);
+    for (entity, draught) in q_draughts.iter() {
+        if can_move && draught.side == side {
+            commands.entity(entity).insert(PickableBundle::default());
+        } else {
+            commands.entity(entity).remove::<PickableBundle>();
+        }
     }
 }
 
 fn place_stone(
-    mut commands: Commands,
     mut er_click_circle: EventReader<EventClickCircle>,
-    q_circles: Query<(Entity, &mut Visibility, &Circle)>,
-    meshes: Res<MeshAssets>,
-    materials: Res<MaterialAssets>,
-    mut turn: ResMut<Turn>,
-    mut game_phase: ResMut<NextState<GamePhase>>,
+    q_circles: Query<&Circle>,
+    turn: Res<Turn>,
     mut game_logic: ResMut<GameLogic>,
-    mut q_draughts: Query<(Entity, &mut Transform, &mut Draught)>,
-    mut q_stones: Query<(Entity, &Stone)>,
-    mut q_watchtowers: Query<(Entity, &Watchtower)>,
-    mut q_debug_squares: Query<Entity, With<DebugSquare>>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
 ) {
-    let black_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::Black)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::White)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let mut black_stones = q_stones
-        .iter_mut()
-        .filter(|(_, go_piece)| go_piece.side == Side::Black)
-        .map(|go_piece| (go_piece.1.i, go_piece.1.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let mut white_stones = q_stones
-        .iter_mut()
-        .filter(|(_, go_piece)| go_piece.side == Side::White)
-        .map(|go_piece| (go_piece.1.i, go_piece.1.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_watchtower = q_watchtowers
-        .iter_mut()
-        .find(|(_, watchtower)| watchtower.side == Side::White);
-    if white_watchtower.is_none() {
-        return;
+    if let Some(er_click) = er_click_circle.read().next() {
+        let circle = q_circles.get(er_click.0).unwrap();
+        if !game_logic
+            .board()
+            .legal_stone_placements(*turn)
+            .contains((circle.i, circle.j))
+        {
+            return;
+        }
+
+        let mv = Move::PlaceStone {
+            i: circle.i,
+            j: circle.j,
+        };
+
+        play_move(mv, &mut game_logic, &mut ew_move_played);
     }
-    let white_watchtower = white_watchtower.unwrap();
-    let white_watchtower = (white_watchtower.1.i, white_watchtower.1.j);
+}
+
+/// Applies `mv` to the rules engine and asks for the result to be rendered. Returns false if
+/// the move is illegal.
+pub fn play_move(
+    mv: Move,
+    game_logic: &mut GameLogic,
+    ew_move_played: &mut EventWriter<EventMovePlayed>,
+) -> bool {
+    let outcome = match game_logic.apply(mv) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            info!("{} rejected: {}", mv, error);
+            return false;
+        }
+    };
 
-    let black_watchtower = q_watchtowers
-        .iter_mut()
-        .find(|(_, watchtower)| watchtower.side == Side::Black);
+    ew_move_played.send(EventMovePlayed(mv, outcome));
+    true
+}
 
-    if black_watchtower.is_none() {
+/// Plays the moves of bots and remote players when it's their turn.
+fn play_controllers(
+    time: Res<Time>,
+    mut since_move: Local<Duration>,
+    mut er_move_played: EventReader<EventMovePlayed>,
+    game_phase: Res<State<GamePhase>>,
+    mut players: ResMut<Players>,
+    mut game_logic: ResMut<GameLogic>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
+) {
+    if er_move_played.read().last().is_some() {
+        *since_move = Duration::ZERO;
+    }
+    *since_move += time.delta();
+
+    // wait until the board shows the step the rules engine is in
+    let board = game_logic.board();
+    if *game_phase.get() != board.phase
+        || matches!(board.phase, GamePhase::Initialize | GamePhase::GameOver)
+        || *since_move < CONTROLLER_MOVE_DELAY
+    {
         return;
     }
-    let black_watchtower = black_watchtower.unwrap();
-    let black_watchtower = (black_watchtower.1.i, black_watchtower.1.j);
 
-    let turn_ = *turn;
-    let side = match turn_ {
-        Turn::Black => Side::Black,
-        Turn::White => Side::White,
+    let controller = players.side_mut(board.turn.into());
+    if let Some(mv) = controller.poll(&game_logic) {
+        info!("{} plays {}", controller.name(), mv);
+        play_move(mv, &mut game_logic, &mut ew_move_played);
+    }
+}
+
+/// Lets every player know what was played.
+fn observe_moves(mut er_move_played: EventReader<EventMovePlayed>, players: Res<Players>) {
+    for EventMovePlayed(mv, _) in er_move_played.read() {
+        players.white.observe(*mv);
+        players.black.observe(*mv);
+    }
+}
+
+/// Follows the rules engine to the next step of the turn once a move is played. The phase and
+/// turn are only ever set here, the action systems just play moves.
+fn start_next_step(
+    mut er_move_played: EventReader<EventMovePlayed>,
+    game_logic: Res<GameLogic>,
+    mut turn: ResMut<Turn>,
+    mut game_phase: ResMut<NextState<GamePhase>>,
+    mut ew_step_started: EventWriter<EventStepStarted>,
+) {
+    let Some(EventMovePlayed(mv, _)) = er_move_played.read().last() else {
+        return;
     };
 
-    for er_click in er_click_circle.read() {
-        let circle = q_circles.get_component::<Circle>(er_click.0).unwrap();
-        commands.spawn((
-            PbrBundle {
-                mesh: meshes.go_piece.clone(),
-                transform: Transform::from_translation(Vec3::new(
-                    circle.i as f32 + 0.5,
-                    0.06,
-                    circle.j as f32 + 0.5,
-                ))
+    let (phase, next_turn) = game_logic.next_state();
+    // offering a draw doesn't use up the step
+    if !mv.is_board_move() && phase != GamePhase::GameOver {
+        return;
+    }
+    *turn = next_turn;
+    game_phase.set(phase);
+
+    // a draught in a chain of takeovers is still in the middle of its step
+    if phase != GamePhase::GameOver && game_logic.board().chain.is_none() {
+        ew_step_started.send(EventStepStarted {
+            phase,
+            turn: next_turn,
+        });
+    }
+}
+
+fn spawn_stone(
+    commands: &mut Commands,
+    meshes: &MeshAssets,
+    materials: &MaterialAssets,
+    (i, j): (usize, usize),
+    side: Side,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.go_piece.clone(),
+            transform: Transform::from_translation(Vec3::new(i as f32 + 0.5, 0.06, j as f32 + 0.5))
                 .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
-                material: match side {
-                    Side::Black => materials.blue.clone(),
-                    _ => materials.yellow.clone(),
-                },
-                ..default()
+            material: match side {
+                Side::Black => materials.blue.clone(),
+                _ => materials.yellow.clone(),
             },
-            Name::new("Stone"),
-            Stone {
-                i: circle.i,
-                j: circle.j,
-                side,
+            ..default()
+        },
+        Name::new("Stone"),
+        Stone { i, j, side },
+    ));
+}
+
+fn spawn_draught(
+    commands: &mut Commands,
+    meshes: &MeshAssets,
+    materials: &MaterialAssets,
+    (i, j): (usize, usize),
+    n: usize,
+    side: Side,
+) {
+    let transform = Transform::from_xyz(i as f32, 0.0, j as f32).with_scale(Vec3::splat(0.1));
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.checkers_piece.clone(),
+            transform,
+            material: match side {
+                Side::Black => materials.black.clone(),
+                _ => materials.white.clone(),
             },
-        ));
+            ..default()
+        },
+        Name::new("Draught"),
+        Draught { i, j, n, side },
+        On::<Pointer<Click>>::send_event::<EventClickDraught>(),
+    ));
+}
+
+fn spawn_placed_watchtower(
+    commands: &mut Commands,
+    meshes: &MeshAssets,
+    materials: &MaterialAssets,
+    (i, j): (usize, usize),
+    side: Side,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.watchtower.clone(),
+            transform: Transform::from_xyz(i as f32, 0.9, j as f32)
+                .with_scale(Vec3::splat(0.1))
+                .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            material: match side {
+                Side::Black => materials.black.clone(),
+                _ => materials.white.clone(),
+            },
+            ..default()
+        },
+        Name::new("Watchtower"),
+        Watchtower { i, j, side },
+        On::<Pointer<Click>>::send_event::<EventClickSquare>(),
+    ));
+}
 
-        match side {
-            Side::Black => black_stones.push((circle.i, circle.j)),
-            Side::White => white_stones.push((circle.i, circle.j)),
+/// Spawns the pieces of a position, for a game that doesn't start from an empty board.
+fn spawn_position(
+    commands: &mut Commands,
+    meshes: &MeshAssets,
+    materials: &MaterialAssets,
+    game_logic: &GameLogic,
+) {
+    let board = game_logic.board();
+    for side in [Side::White, Side::Black] {
+        let army = board.army(side);
+        if let Some(watchtower) = army.watchtower {
+            spawn_placed_watchtower(commands, meshes, materials, watchtower, side);
+        }
+        for (n, draught) in army.draughts.iter().enumerate() {
+            spawn_draught(commands, meshes, materials, draught, n, side);
+        }
+        for stone in army.stones.iter() {
+            spawn_stone(commands, meshes, materials, stone, side);
         }
+    }
+}
 
-        let (convex_set, stone_removals) = game_logic.legal_go_moves(
-            turn_,
-            black_draughts.clone(),
-            white_draughts.clone(),
-            white_stones.clone(),
-            black_stones.clone(),
-            white_watchtower,
-            black_watchtower,
-        );
+/// Brings entities in line with what a move did on the board.
+#[allow(clippy::too_many_arguments)]
+fn render_outcome(
+    mut commands: Commands,
+    mut er_move_played: EventReader<EventMovePlayed>,
+    meshes: Res<MeshAssets>,
+    materials: Res<MaterialAssets>,
+    q_draughts: Query<(Entity, &Transform, &Draught)>,
+    q_stones: Query<(Entity, &Stone)>,
+    q_watchtowers: Query<(Entity, &Transform, &Watchtower)>,
+    q_debug_squares: Query<Entity, With<DebugSquare>>,
+    game_logic: Res<GameLogic>,
+) {
+    let rules = &game_logic.board().rules;
+    for EventMovePlayed(mv, outcome) in er_move_played.read() {
+        let side = outcome.side;
+
+        if let Some(center) = outcome.watchtower {
+            let material = match side {
+                Side::Black => materials.black.clone(),
+                _ => materials.white.clone(),
+            };
+
+            // the preview pieces move where the watchtower was placed, which is somewhere else
+            // when it wasn't placed with the pointer
+            for (entity, transform, draught) in q_draughts.iter() {
+                if draught.side != side {
+                    continue;
+                }
+                let square = rules.watchtower_draughts.get(draught.n).map(|(di, dj)| {
+                    (
+                        (center.0 as i32 + di) as usize,
+                        (center.1 as i32 + dj) as usize,
+                    )
+                });
+                // preview draughts off the board weren't placed
+                let Some(square) = square.filter(|square| outcome.placed_draughts.contains(square))
+                else {
+                    commands.entity(entity).despawn_recursive();
+                    continue;
+                };
+                let mut transform = *transform;
+                transform.translation.x = square.0 as f32;
+                transform.translation.z = square.1 as f32;
+                commands.entity(entity).insert((
+                    PickableBundle::default(),
+                    material.clone(),
+                    transform,
+                    Draught {
+                        i: square.0,
+                        j: square.1,
+                        ..*draught
+                    },
+                ));
+            }
 
-        // despawn debug squares
-        for entity in q_debug_squares.iter_mut() {
-            commands.entity(entity).despawn_recursive();
+            for (entity, transform, watchtower) in q_watchtowers.iter() {
+                if watchtower.side != side {
+                    continue;
+                }
+                let mut transform = *transform;
+                transform.translation.x = center.0 as f32;
+                transform.translation.z = center.1 as f32;
+                commands.entity(entity).insert((
+                    material.clone(),
+                    transform,
+                    Watchtower {
+                        i: center.0,
+                        j: center.1,
+                        side,
+                    },
+                ));
+            }
         }
 
-        // remove opposite stones
-        let enemy_stones = match side {
-            Side::Black => white_stones.clone(),
-            Side::White => black_stones.clone(),
-        };
+        for stone in outcome.placed_stones.iter() {
+            spawn_stone(&mut commands, &meshes, &materials, *stone, side);
+        }
 
-        let enemy_draughts = match side {
-            Side::Black => white_draughts.clone(),
-            Side::White => black_draughts.clone(),
-        };
+        for (stone_side, (i, j)) in outcome.removed_stones.iter() {
+            for (entity, stone) in q_stones.iter() {
+                if stone.side == *stone_side && stone.i == *i && stone.j == *j {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
 
-        for (i, j) in stone_removals.iter() {
-            for enemy_stone in enemy_stones.iter() {
-                if !(enemy_stone.0 == *i && enemy_stone.1 == *j) {
-                    continue;
+        for (draught_side, (i, j)) in outcome.removed_draughts.iter() {
+            for (entity, _, draught) in q_draughts.iter() {
+                if draught.side == *draught_side && draught.i == *i && draught.j == *j {
+                    commands.entity(entity).despawn_recursive();
                 }
+            }
+        }
 
-                let stone_entity = q_stones
-                    .iter()
-                    .find(|(_, stone)| (stone.i == enemy_stone.0 && stone.j == enemy_stone.1));
-                if stone_entity.is_some() {
-                    let stone_entity = stone_entity.unwrap().0;
-                    commands.entity(stone_entity).despawn_recursive();
+        for watchtower_side in outcome.removed_watchtowers.iter() {
+            for (entity, _, watchtower) in q_watchtowers.iter() {
+                if watchtower.side == *watchtower_side {
+                    commands.entity(entity).despawn_recursive();
                 }
             }
         }
 
-        for convex in convex_set.iter() {
-            for (i, j) in convex.iter() {
-                // takeover pieces
-                for enemy_draught in enemy_draughts.iter() {
-                    if !(enemy_draught.0 == *i && enemy_draught.1 == *j) {
-                        continue;
-                    }
-
-                    let enemy_draught = q_draughts
-                        .iter()
-                        .find(|d| d.2.i == enemy_draught.0 && d.2.j == enemy_draught.1)
-                        .unwrap();
-
-                    let enemy_draught_entity = enemy_draught.0;
-                    let enemy_draught = enemy_draught.2;
-                    let n_draughts = q_draughts.iter().filter(|d| d.2.side == side).count();
-
-                    commands.entity(enemy_draught_entity).despawn_recursive();
-
-                    let transform =
-                        Transform::from_xyz(enemy_draught.i as f32, 0.0, enemy_draught.j as f32)
-                            .with_scale(Vec3::splat(0.1));
-                    let draught = Draught {
-                        i: enemy_draught.i,
-                        j: enemy_draught.j,
-                        n: (n_draughts + 1) as i8,
-                        side,
-                    };
-
-                    commands.spawn((
-                        PbrBundle {
-                            mesh: meshes.checkers_piece.clone(),
-                            transform,
-                            material: match side {
-                                Side::Black => materials.black.clone(),
-                                _ => materials.white.clone(),
-                            },
-                            ..default()
-                        },
-                        Name::new("Draught"),
-                        draught,
-                        On::<Pointer<Click>>::send_event::<EventClickDraught>(),
-                    ));
+        // takeover pieces
+        let mut n_draught = q_draughts
+            .iter()
+            .filter(|(_, _, draught)| draught.side == side)
+            .map(|(_, _, draught)| draught.n)
+            .max()
+            .unwrap_or(0);
+        for (i, j) in outcome.taken_over_draughts.iter() {
+            for (entity, _, draught) in q_draughts.iter() {
+                if draught.side != side && draught.i == *i && draught.j == *j {
+                    commands.entity(entity).despawn_recursive();
                 }
+            }
+
+            n_draught += 1;
+            spawn_draught(
+                &mut commands,
+                &meshes,
+                &materials,
+                (*i, *j),
+                n_draught,
+                side,
+            );
+        }
+
+        if let Some((from, to)) = outcome.moved_draught {
+            let draught = q_draughts
+                .iter()
+                .find(|(_, _, d)| d.side == side && (d.i, d.j) == from);
+
+            if let Some((entity, transform, draught)) = draught {
+                let tween = Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    Duration::from_millis(1000),
+                    TransformPositionWithYJumpLens {
+                        start: transform.translation,
+                        end: Vec3::new(to.0 as f32, 0.0, to.1 as f32),
+                    },
+                );
+
+                commands.entity(entity).insert((
+                    Animator::new(tween),
+                    Draught {
+                        i: to.0,
+                        j: to.1,
+                        ..*draught
+                    },
+                ));
+            }
+        }
+
+        if let Move::PlaceStone { .. } = mv {
+            // despawn debug squares
+            for entity in q_debug_squares.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
 
-                // spawn debug square
+            for (i, j) in outcome.territory.iter() {
                 commands.spawn((
                     PbrBundle {
                         mesh: meshes.square_plane.clone(),
@@ -253,25 +491,35 @@ fn place_stone(
                 ));
             }
         }
-
-        game_logic.log(GamePhase::PlaceGoPiece, *turn);
-        let (next_phase, next_turn) = game_logic.next_state();
-        *turn = next_turn;
-        game_phase.set(next_phase);
-        return;
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_board(
     mut commands: Commands,
     materials: Res<MaterialAssets>,
     meshes: Res<MeshAssets>,
+    board_config: Res<BoardConfig>,
+    rules: Res<RuleSet>,
+    mut game_logic: ResMut<GameLogic>,
+    mut resumed_game: ResMut<ResumedGame>,
+    mut turn: ResMut<Turn>,
     mut game_phase: ResMut<NextState<GamePhase>>,
+    mut ew_step_started: EventWriter<EventStepStarted>,
 ) {
+    let size = board_config.size;
+    match resumed_game.0.take() {
+        Some(resumed) => {
+            *game_logic = resumed;
+            spawn_position(&mut commands, &meshes, &materials, &game_logic);
+        }
+        None => *game_logic = GameLogic::with_rules(*board_config, rules.clone()),
+    }
+
     // spawn checkerboard
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            let n = i * BOARD_SIZE + j;
+    for i in 0..size {
+        for j in 0..size {
+            let n = i * size + j;
             let material = match n % 2 {
                 0 => materials.black.clone(),
                 _ => materials.white.clone(),
@@ -294,8 +542,8 @@ fn spawn_board(
     }
 
     // spawn go pieces intersections
-    for i in 0..BOARD_SIZE - 1 {
-        for j in 0..BOARD_SIZE - 1 {
+    for i in 0..size - 1 {
+        for j in 0..size - 1 {
             commands.spawn((
                 PbrBundle {
                     mesh: meshes.circle.clone(),
@@ -317,10 +565,20 @@ fn spawn_board(
         }
     }
 
-    game_phase.set(GamePhase::PlaceWatchtower);
+    let (phase, first_turn) = game_logic.next_state();
+    *turn = first_turn;
+    game_phase.set(phase);
+    ew_step_started.send(EventStepStarted {
+        phase,
+        turn: first_turn,
+    });
 }
 
-fn spawn_camera(mut commands: Commands) {
+fn spawn_camera(mut commands: Commands, board_config: Res<BoardConfig>) {
+    let size = board_config.size as f32;
+    // camera was framed for 19x19, keep the same view on smaller boards
+    let scale = size / 19.0;
+
     // Light
     commands.spawn(PointLightBundle {
         point_light: PointLight {
@@ -328,12 +586,13 @@ fn spawn_camera(mut commands: Commands) {
             shadows_enabled: true,
             ..Default::default()
         },
-        transform: Transform::from_xyz(BOARD_SIZE as f32 / 2.0, 10.0, BOARD_SIZE as f32 / 2.0),
+        transform: Transform::from_xyz(size / 2.0, 10.0, size / 2.0),
         ..Default::default()
     });
 
-    let camera_transform = Transform::from_translation(Vec3::new(-5.0, 30.0, 20.0))
-        .looking_at(Vec3::new(19.0 / 2.0, 0.0, 19.0 / 2.0), Vec3::Y);
+    let camera_transform =
+        Transform::from_translation(Vec3::new(-5.0 * scale, 30.0 * scale, 20.0 * scale))
+            .looking_at(Vec3::new(size / 2.0, 0.0, size / 2.0), Vec3::Y);
 
     commands.spawn((
         Camera3dBundle {
@@ -345,229 +604,118 @@ fn spawn_camera(mut commands: Commands) {
     ));
 }
 
+#[allow(clippy::too_many_arguments)]
 fn place_watchtower(
-    mut commands: Commands,
-    materials: Res<MaterialAssets>,
     mut er_hover_square: EventReader<EventHoverSquare>,
     mut er_click_square: EventReader<EventClickSquare>,
-    _er_click_watchtower: EventReader<EventClickWatchtower>,
-    q_squares: Query<Entity, &Square>,
+    q_squares: Query<&Square>,
     mut q_pieces: Query<(Entity, &mut Transform, &mut Draught)>,
     mut q_watchtower: Query<(Entity, &mut Transform, &mut Watchtower), Without<Draught>>,
-    mut turn: ResMut<Turn>,
-    mut game_phase: ResMut<NextState<GamePhase>>,
+    turn: Res<Turn>,
     mut game_logic: ResMut<GameLogic>,
-    meshes: Res<MeshAssets>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
 ) {
-    let turn_ = *turn;
-
-    let side = match turn_ {
-        Turn::Black => Side::Black,
-        Turn::White => Side::White,
-    };
-
-    let mut stop = |center: (usize, usize)| -> Turn {
-        let material = match side {
-            Side::Black => materials.black.clone(),
-            _ => materials.white.clone(),
-        };
-
-        for (entity, _, draught) in q_pieces.iter() {
-            if draught.side != side {
-                continue;
-            }
-            commands
-                .entity(entity)
-                .insert((PickableBundle::default(), material.clone()));
-        }
-
-        for (entity, _, watchtower) in q_watchtower.iter() {
-            if watchtower.side != side {
-                continue;
-            }
-            commands.entity(entity).insert(material.clone());
-        }
-
-        // place stones around watchtower
-        let initial_stone_positions: Vec<(i32, i32)> = vec![
-            (1, 1),
-            (1, 0),
-            (1, -1),
-            (1, -2),
-            (0, 1),
-            (0, -2),
-            (-1, 1),
-            (-1, -2),
-            (-2, 1),
-            (-2, 0),
-            (-2, -1),
-            (-2, -2),
-        ];
-
-        for isp in initial_stone_positions {
-            commands.spawn((
-                PbrBundle {
-                    mesh: meshes.go_piece.clone(),
-                    transform: Transform::from_translation(Vec3::new(
-                        (center.0 as i32 + isp.0) as f32 + 0.5,
-                        0.05,
-                        (center.1 as i32 + isp.1) as f32 + 0.5,
-                    ))
-                    .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
-                    material: match side {
-                        Side::Black => materials.blue.clone(),
-                        _ => materials.yellow.clone(),
-                    },
-                    ..default()
-                },
-                Name::new("Stone"),
-                Stone {
-                    i: (center.0 as i32 + isp.0) as usize,
-                    j: (center.1 as i32 + isp.1) as usize,
-                    side,
-                },
-            ));
-        }
-
-        game_logic.log(GamePhase::PlaceWatchtower, turn_);
-        let (next_phase, next_turn) = game_logic.next_state();
-        if next_phase == GamePhase::PlaceWatchtower {
-            game_phase.set(GamePhase::TriggerPlaceWatchtower);
-        } else {
-            game_phase.set(next_phase);
-        }
-        *turn = next_turn;
-
-        next_turn
-    };
+    let side: Side = (*turn).into();
+    let placements = game_logic.board().legal_tower_placements(side);
 
+    // clicking the preview tower or a square places the watchtower there
     for click in er_click_square.read() {
-        let watchtower = q_watchtower.get_component::<Watchtower>(click.0);
-
-        if watchtower.is_ok() {
-            let watchtower = watchtower.unwrap();
-            let center = (watchtower.i, watchtower.j);
-            *turn = stop(center);
+        let center = if let Ok(watchtower) = q_watchtower.get_component::<Watchtower>(click.0) {
+            (watchtower.i, watchtower.j)
+        } else if let Ok(square) = q_squares.get(click.0) {
+            (square.i, square.j)
+        } else {
+            continue;
+        };
 
-            return;
+        if !placements.contains(center) {
+            info!("Watchtower can't be placed on {:?}", center);
+            continue;
         }
-    }
 
-    let opposite_side = match side {
-        Side::Black => Side::White,
-        _ => Side::Black,
-    };
-
-    let opposite_watchtower = q_watchtower
-        .iter_mut()
-        .find(|(_, _, watchtower)| watchtower.side == opposite_side);
-    let mut opposite_watchtower_position = (-128.0, 128.0);
-    if opposite_watchtower.is_some() {
-        let opp_watchtower = opposite_watchtower.unwrap().2;
-        opposite_watchtower_position = (opp_watchtower.i as f32, opp_watchtower.j as f32);
+        play_move(
+            Move::PlaceWatchtower {
+                i: center.0,
+                j: center.1,
+            },
+            &mut game_logic,
+            &mut ew_move_played,
+        );
+        return;
     }
 
     for hover in er_hover_square.read() {
-        let square = q_squares.get_component::<Square>(hover.0).unwrap();
+        let Ok(square) = q_squares.get(hover.0) else {
+            continue;
+        };
         let center = (square.i, square.j);
+        if !placements.contains(center) {
+            continue;
+        }
 
         for (_entity, mut transform, mut piece) in q_pieces.iter_mut() {
             if piece.side != side {
                 continue;
             }
 
-            // don't allow placing pieces on the edge
-            if center.0 > 15 || center.1 > 15 || center.0 < 3 || center.1 < 3 {
+            let Some((di, dj)) = game_logic.board().rules.watchtower_draughts.get(piece.n) else {
                 continue;
-            }
+            };
+            let (i, j) = (center.0 as i32 + di, center.1 as i32 + dj);
 
-            // don't allow placing pieces on the opposite watchtower
-            let distance = ((center.0 as f32 - opposite_watchtower_position.0).powi(2)
-                + (center.1 as f32 - opposite_watchtower_position.1).powi(2))
-            .sqrt();
+            transform.translation.x = i as f32;
+            transform.translation.z = j as f32;
+            piece.i = i as usize;
+            piece.j = j as usize;
+        }
 
-            if distance < 5.0 {
+        for (_entity, mut transform, mut watchtower) in q_watchtower.iter_mut() {
+            if watchtower.side != side {
                 continue;
             }
 
-            match piece.n {
-                0 => {
-                    transform.translation.x = center.0 as f32 - 1.0;
-                    transform.translation.z = center.1 as f32 - 1.0;
-                    piece.i = center.0 - 1;
-                    piece.j = center.1 - 1;
-                }
-
-                1 => {
-                    transform.translation.x = center.0 as f32 - 1.0;
-                    transform.translation.z = center.1 as f32;
-                    piece.i = center.0 - 1;
-                    piece.j = center.1;
-                }
-
-                2 => {
-                    transform.translation.x = center.0 as f32 - 1.0;
-                    transform.translation.z = center.1 as f32 + 1.0;
-                    piece.i = center.0 - 1;
-                    piece.j = center.1 + 1;
-                }
-
-                3 => {
-                    transform.translation.x = center.0 as f32;
-                    transform.translation.z = center.1 as f32 - 1.0;
-                    piece.i = center.0;
-                    piece.j = center.1 - 1;
-                }
-
-                4 => {
-                    transform.translation.x = center.0 as f32;
-                    transform.translation.z = center.1 as f32;
-                    piece.i = center.0;
-                    piece.j = center.1;
-                }
-
-                5 => {
-                    transform.translation.x = center.0 as f32;
-                    transform.translation.z = center.1 as f32 + 1.0;
-                    piece.i = center.0;
-                    piece.j = center.1 + 1;
-                }
-
-                6 => {
-                    transform.translation.x = center.0 as f32 + 1.0;
-                    transform.translation.z = center.1 as f32 - 1.0;
-                    piece.i = center.0 + 1;
-                    piece.j = center.1 - 1;
-                }
-
-                7 => {
-                    transform.translation.x = center.0 as f32 + 1.0;
-                    transform.translation.z = center.1 as f32;
-                    piece.i = center.0 + 1;
-                    piece.j = center.1;
-                }
+            transform.translation.x = center.0 as f32;
+            transform.translation.z = center.1 as f32;
+            watchtower.i = center.0;
+            watchtower.j = center.1;
+        }
+    }
+}
 
-                8 => {
-                    transform.translation.x = center.0 as f32 + 1.0;
-                    transform.translation.z = center.1 as f32 + 1.0;
-                    piece.i = center.0 + 1;
-                    piece.j = center.1 + 1;
-                }
+/// Tints the squares where the side to move can put its watchtower.
+fn show_tower_placements(
+    mut commands: Commands,
+    mut er_step_started: EventReader<EventStepStarted>,
+    meshes: Res<MeshAssets>,
+    materials: Res<MaterialAssets>,
+    game_logic: Res<GameLogic>,
+    q_placement_squares: Query<Entity, With<TowerPlacementSquare>>,
+) {
+    let Some(step) = er_step_started.read().last() else {
+        return;
+    };
 
-                _ => {}
-            }
+    for entity in q_placement_squares.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
 
-            for (_entity, mut transform, mut watchtower) in q_watchtower.iter_mut() {
-                if watchtower.side != side {
-                    continue;
-                }
+    if step.phase != GamePhase::PlaceWatchtower {
+        return;
+    }
 
-                transform.translation.x = center.0 as f32;
-                transform.translation.z = center.1 as f32;
-                watchtower.i = center.0;
-                watchtower.j = center.1;
-            }
-        }
+    let placements = game_logic.board().legal_tower_placements(step.turn.into());
+    for (i, j) in placements.iter() {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.square_plane.clone(),
+                material: materials.blue.clone(),
+                transform: Transform::from_translation(Vec3::new(i as f32, 0.001, j as f32)),
+                ..default()
+            },
+            Name::new("TowerPlacementSquare"),
+            TowerPlacementSquare,
+            Pickable::IGNORE,
+        ));
     }
 }
 
@@ -592,9 +740,8 @@ fn select_draught(
 
         selected_draught.n = Some(draught.n);
 
-        for (_, mut v, _) in q_nuke_draught_button.iter_mut() {
+        if let Some((_, mut v, _)) = q_nuke_draught_button.iter_mut().next() {
             *v = Visibility::Visible;
-            break;
         }
     }
 }
@@ -617,225 +764,139 @@ impl Lens<Transform> for TransformPositionWithYJumpLens {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn move_draught(
-    mut commands: Commands,
     mut er_click_square: EventReader<EventClickSquare>,
-    mut q_draughts: Query<(Entity, &mut Transform, &mut Draught)>,
-    mut q_stones: Query<&Stone>,
-    mut q_watchtowers: Query<&Watchtower>,
-    q_squares: Query<(Entity, &mut Transform, &mut Square), Without<Draught>>,
+    q_draughts: Query<&Draught>,
+    q_squares: Query<&Square>,
     mut selected_draught: ResMut<SelectedDraught>,
-    mut turn: ResMut<Turn>,
+    turn: Res<Turn>,
     mut game_logic: ResMut<GameLogic>,
-    mut game_phase: ResMut<NextState<GamePhase>>,
-    materials: Res<MaterialAssets>,
-    meshes: Res<MeshAssets>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
     mut q_nuke_draught_button: Query<(Entity, &mut Visibility, &ButtonNukeDraught)>,
 ) {
     if selected_draught.n.is_none() {
         return;
     }
 
-    let turn_ = *turn;
-    let side = match turn_ {
-        Turn::Black => Side::Black,
-        Turn::White => Side::White,
-    };
-
-    let black_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::Black)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::White)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let black_stones = q_stones
-        .iter_mut()
-        .filter(|go_piece| go_piece.side == Side::Black)
-        .map(|go_piece| (go_piece.i, go_piece.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_stones = q_stones
-        .iter_mut()
-        .filter(|go_piece| go_piece.side == Side::White)
-        .map(|go_piece| (go_piece.i, go_piece.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_watchtower = q_watchtowers
-        .iter_mut()
-        .find(|watchtower| watchtower.side == Side::White)
-        .unwrap();
-    let white_watchtower = (white_watchtower.i, white_watchtower.j);
-
-    let black_watchtower = q_watchtowers
-        .iter_mut()
-        .find(|watchtower| watchtower.side == Side::Black)
-        .unwrap();
-    let black_watchtower = (black_watchtower.i, black_watchtower.j);
+    let side: Side = (*turn).into();
 
     for click in er_click_square.read() {
-        let square = q_squares.get_component::<Square>(click.0);
+        let square = q_squares.get(click.0);
         if square.is_err() {
             continue;
         }
-
-        let square = q_squares.get_component::<Square>(click.0).unwrap();
+        let square = square.unwrap();
 
         let draught = q_draughts
             .iter()
-            .find(|d| d.2.n == selected_draught.n.unwrap() && d.2.side == side)
+            .find(|d| d.n == selected_draught.n.unwrap() && d.side == side)
             .unwrap();
 
-        let draught_position = draught.1.translation;
-        let draught_entity = draught.0;
-        let draught = draught.2;
-
-        let (possible_moves, possible_movetypes, takeovers, _stone_removals) = game_logic
-            .legal_draught_moves(
-                turn_,
-                (draught.i, draught.j),
-                black_draughts,
-                white_draughts,
-                white_stones,
-                black_stones,
-                white_watchtower,
-                black_watchtower,
-            );
+        let mv = game_logic
+            .board()
+            .draught_moves((draught.i, draught.j))
+            .into_iter()
+            .find(|mv| mv.destination() == Some((square.i, square.j)));
 
-        if !possible_moves.contains(&(square.i, square.j)) {
-            println!("Illegal move");
+        let Some(mv) = mv else {
             return;
-        }
-
-        let possible_move_index = possible_moves
-            .iter()
-            .position(|(i, j)| *i == square.i && *j == square.j)
-            .unwrap();
-        let move_type = possible_movetypes[possible_move_index];
-
-        if move_type == CheckersMoveType::DraughtTakeOver {
-            let takeover = takeovers[possible_move_index];
-            let enemy_draught = q_draughts
-                .iter()
-                .find(|d| d.2.i == takeover.0 && d.2.j == takeover.1)
-                .unwrap();
-
-            let enemy_draught_entity = enemy_draught.0;
-            let enemy_draught = enemy_draught.2;
-            let n_draughts = q_draughts.iter().filter(|d| d.2.side == side).count();
-
-            commands.entity(enemy_draught_entity).despawn_recursive();
-
-            let transform =
-                Transform::from_xyz(enemy_draught.i as f32, 0.0, enemy_draught.j as f32)
-                    .with_scale(Vec3::splat(0.1));
-            let draught = Draught {
-                i: enemy_draught.i,
-                j: enemy_draught.j,
-                n: (n_draughts + 1) as i8,
-                side,
-            };
+        };
 
-            commands.spawn((
-                PbrBundle {
-                    mesh: meshes.checkers_piece.clone(),
-                    transform,
-                    material: match side {
-                        Side::Black => materials.black.clone(),
-                        _ => materials.white.clone(),
-                    },
-                    ..default()
-                },
-                Name::new("Draught"),
-                draught,
-                On::<Pointer<Click>>::send_event::<EventClickDraught>(),
-            ));
+        if !play_move(mv, &mut game_logic, &mut ew_move_played) {
+            return;
         }
 
-        let square_position = q_squares
-            .get_component::<Transform>(click.0)
-            .unwrap()
-            .translation;
-
-        let tween = Tween::new(
-            EaseFunction::QuadraticInOut,
-            Duration::from_millis(1000),
-            TransformPositionWithYJumpLens {
-                start: draught_position,
-                end: square_position,
-            },
-        );
-
-        commands.entity(draught_entity).insert((
-            Animator::new(tween),
-            Draught {
-                i: square.i,
-                j: square.j,
-                ..*draught
-            },
-        ));
-
-        selected_draught.n = None;
-
         for (_, mut v, _) in q_nuke_draught_button.iter_mut() {
             *v = Visibility::Hidden;
         }
 
-        game_logic.log(GamePhase::MoveDraught, *turn);
-        let (next_phase, next_turn) = game_logic.next_state();
-        *turn = next_turn;
-        game_phase.set(next_phase);
+        // the draught stays selected while it's taking over in a chain
+        if game_logic.board().chain.is_none() {
+            selected_draught.n = None;
+        }
 
         return;
     }
 }
 
-fn prepare_move_draught(
+/// Marks the squares the draught in a chain of takeovers can jump to next.
+#[allow(clippy::too_many_arguments)]
+fn highlight_chain(
     mut commands: Commands,
-    mut q_circles: Query<(Entity, &mut Visibility, &Circle)>,
-    mut q_draughts: Query<(Entity, &mut Draught)>,
-    turn: ResMut<Turn>,
+    mut er_move_played: EventReader<EventMovePlayed>,
+    meshes: Res<MeshAssets>,
+    materials: Res<MaterialAssets>,
+    game_logic: Res<GameLogic>,
+    q_chain_squares: Query<Entity, With<ChainSquare>>,
+    mut q_end_chain_button: Query<&mut Visibility, With<ButtonEndChain>>,
+    players: Res<Players>,
 ) {
-    let turn = *turn;
-    let side = match turn {
-        Turn::Black => Side::Black,
-        Turn::White => Side::White,
+    if er_move_played.read().last().is_none() {
+        return;
+    }
+
+    for entity in q_chain_squares.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let board = game_logic.board();
+    let moves = match board.chain {
+        Some(chain) => board.draught_moves(chain),
+        None => vec![],
     };
 
-    for (entity, mut visibility, _) in q_circles.iter_mut() {
-        *visibility = Visibility::Hidden;
-        commands.entity(entity).remove::<PickableBundle>();
+    for (i, j) in moves.iter().filter_map(|mv| mv.destination()) {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.square_plane.clone(),
+                material: materials.yellow.clone(),
+                transform: Transform::from_translation(Vec3::new(i as f32, 0.001, j as f32)),
+                ..default()
+            },
+            Name::new("ChainSquare"),
+            ChainSquare,
+            Pickable::IGNORE,
+        ));
     }
 
-    for (entity, draught) in q_draughts.iter_mut() {
-        if draught.side != side {
-            commands.entity(entity).remove::<PickableBundle>();
+    let can_stop = board.chain.is_some()
+        && board.legal_moves().contains(&Move::Pass)
+        && players.side(board.turn.into()).is_human();
+    for mut visibility in q_end_chain_button.iter_mut() {
+        *visibility = if can_stop {
+            Visibility::Visible
         } else {
-            commands.entity(entity).insert(PickableBundle::default());
-        }
+            Visibility::Hidden
+        };
     }
 }
 
 fn spawn_watchtower(
     mut commands: Commands,
+    mut er_step_started: EventReader<EventStepStarted>,
     meshes: Res<MeshAssets>,
     materials: Res<MaterialAssets>,
-    turn: ResMut<Turn>,
+    board_config: Res<BoardConfig>,
+    game_logic: Res<GameLogic>,
     mut q_circles: Query<(Entity, &mut Visibility, &Circle)>,
 ) {
+    let Some(step) = er_step_started.read().last() else {
+        return;
+    };
+    if step.phase != GamePhase::PlaceWatchtower {
+        return;
+    }
+
     for (entity, mut visibility, _) in q_circles.iter_mut() {
         *visibility = Visibility::Hidden;
         commands.entity(entity).remove::<PickableBundle>();
     }
 
-    let center = (10.0, 10.0);
+    let center = board_config.center();
+    let center = (center.0 as f32, center.1 as f32);
 
-    let turn_ = *turn;
+    let turn_ = step.turn;
     let side = match turn_ {
         Turn::Black => Side::Black,
         Turn::White => Side::White,
@@ -866,33 +927,21 @@ fn spawn_watchtower(
         On::<Pointer<Click>>::send_event::<EventClickSquare>(),
     ));
 
-    for i in 0..9 {
-        if i == 4 {
-            continue;
-        }
-
-        let piece_position = match i {
-            0 => (center.0 - 1.0, center.1 - 1.0),
-            1 => (center.0 - 1.0, center.1),
-            2 => (center.0 - 1.0, center.1 + 1.0),
-
-            3 => (center.0, center.1 - 1.0),
-            4 => (center.0, center.1),
-            5 => (center.0, center.1 + 1.0),
-
-            6 => (center.0 + 1.0, center.1 - 1.0),
-            7 => (center.0 + 1.0, center.1),
-            8 => (center.0 + 1.0, center.1 + 1.0),
-
-            _ => (0.0, 0.0),
-        };
+    for (n, (di, dj)) in game_logic
+        .board()
+        .rules
+        .watchtower_draughts
+        .iter()
+        .enumerate()
+    {
+        let piece_position = (center.0 + *di as f32, center.1 + *dj as f32);
 
         let transform = Transform::from_xyz(piece_position.0, 0.0, piece_position.1)
             .with_scale(Vec3::splat(0.1));
         let draught = Draught {
             i: piece_position.0 as usize,
             j: piece_position.1 as usize,
-            n: i,
+            n,
             side,
         };
 
@@ -910,50 +959,58 @@ fn spawn_watchtower(
     }
 }
 
-fn check_game_termination(
-    q_watchtowers: Query<(Entity, &Watchtower)>,
-    mut text_query: Query<(&mut Text, &mut Visibility, &NextMoveText)>,
-    mut next_game_phase: ResMut<NextState<GamePhase>>,
-    current_game_phase: Res<State<GamePhase>>,
-) {
-    let game_phase = current_game_phase.get();
-    if *game_phase == GamePhase::Initialize
-        || *game_phase == GamePhase::PlaceWatchtower
-        || *game_phase == GamePhase::TriggerPlaceWatchtower
-    {
+/// Saves the game after every move, so it can be continued from the menu. A finished game
+/// can't be continued, so its save is removed.
+fn autosave_game(game_logic: Res<GameLogic>, players: Res<Players>) {
+    let Some(path) = save_path() else {
         return;
-    }
-
-    let n_white_towers = q_watchtowers
-        .iter()
-        .filter(|(_, w)| w.side == Side::White)
-        .count();
-
-    let n_black_towers = q_watchtowers
-        .iter()
-        .filter(|(_, w)| w.side == Side::Black)
-        .count();
+    };
 
-    if n_white_towers != 0 && n_white_towers != 0 {
+    if game_logic.result().is_some() {
+        if path.exists() {
+            if let Err(err) = std::fs::remove_file(&path) {
+                warn!("can't remove save {}: {err}", path.display());
+            }
+        }
         return;
     }
 
-    let mut gameover_text = "";
-
-    if n_white_towers == 0 {
-        gameover_text = "Black Won!"
+    let saved_game = SavedGame::new(
+        &game_logic,
+        players.side(Side::White).name(),
+        players.side(Side::Black).name(),
+    );
+    if let Err(err) = saved_game.write(&path) {
+        warn!("can't save game to {}: {err}", path.display());
     }
+}
 
-    if n_black_towers == 0 {
-        gameover_text = "White Won!"
+fn check_game_termination(
+    game_logic: Res<GameLogic>,
+    mut er_move_played: EventReader<EventMovePlayed>,
+    mut text_query: Query<(&mut Text, &mut Visibility, &NextMoveText)>,
+) {
+    if er_move_played.read().last().is_none() {
+        return;
     }
 
+    let result_text = match game_logic.result() {
+        Some(GameResult::Winner(Side::Black)) => "Black Won!",
+        Some(GameResult::Winner(Side::White)) => "White Won!",
+        Some(GameResult::Draw) => "Draw!",
+        None => return,
+    };
+    let gameover_text = match game_logic.ending() {
+        Some(Ending::Resignation(side)) => format!("{side:?} Resigned\n{result_text}"),
+        Some(Ending::Agreement) => "Draw Agreed!".into(),
+        Some(Ending::Passes) => format!("Both Passed\n{result_text}"),
+        _ => result_text.into(),
+    };
+
     for (mut text, mut v, _tag) in text_query.iter_mut() {
-        text.sections[0].value = gameover_text.into();
+        text.sections[0].value = gameover_text.clone();
         *v = Visibility::Visible;
     }
-
-    next_game_phase.set(GamePhase::GameOver);
 }
 
 // --------------
@@ -966,48 +1023,88 @@ impl Plugin for WatchtowerPlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GamePhase>();
         app.init_resource::<Turn>();
+        app.init_resource::<BoardConfig>();
+        app.init_resource::<RuleSet>();
         app.add_plugins(DefaultPickingPlugins)
             .add_systems(Update, bevy_mod_picking::debug::hide_pointer_text)
             .add_systems(Startup, init_buttons)
-            .add_systems(Update, nuke_draught_button_system)
-            .add_systems(Startup, init_game_over_text)
-            .add_systems(Update, check_game_termination)
             .add_systems(
-                OnEnter(GameState::Watchtower),
-                (spawn_camera, spawn_board, init_stats_text),
+                Update,
+                (
+                    (
+                        nuke_draught_button_system,
+                        end_chain_button_system,
+                        game_action_button_system,
+                    )
+                        .run_if(human_to_move),
+                    show_game_action_buttons.run_if(
+                        on_event::<EventMovePlayed>().or_else(on_event::<EventStepStarted>()),
+                    ),
+                ),
             )
-            .add_systems(OnEnter(GamePhase::PlaceWatchtower), spawn_watchtower)
+            .add_systems(Startup, init_game_over_text)
+            .add_systems(Update, check_game_termination)
             .add_systems(
-                OnEnter(GamePhase::TriggerPlaceWatchtower),
-                |mut game_phase: ResMut<NextState<GamePhase>>| {
-                    game_phase.set(GamePhase::PlaceWatchtower);
-                },
+                PostUpdate,
+                (
+                    render_outcome,
+                    highlight_chain,
+                    observe_moves,
+                    start_next_step,
+                    clear_draught_selection,
+                    autosave_game.run_if(on_event::<EventMovePlayed>()),
+                ),
             )
             .add_systems(
-                OnEnter(GamePhase::PlaceGoPiece),
-                (spawn_go_piece, show_stats),
+                OnEnter(GameState::Watchtower),
+                (spawn_camera, spawn_board, init_stats_text),
             )
             .add_systems(
-                OnEnter(GamePhase::MoveDraught),
-                (prepare_move_draught, show_stats),
+                Update,
+                (
+                    spawn_watchtower,
+                    show_tower_placements,
+                    prepare_step,
+                    show_stats.run_if(on_event::<EventStepStarted>()),
+                ),
             )
             .add_systems(
                 Update,
-                place_watchtower.run_if(in_state(GamePhase::PlaceWatchtower)),
+                place_watchtower
+                    .run_if(in_state(GamePhase::PlaceWatchtower))
+                    .run_if(human_to_move),
             )
             .add_systems(
                 Update,
-                place_stone.run_if(in_state(GamePhase::PlaceGoPiece)),
+                place_stone
+                    .run_if(
+                        in_state(GamePhase::PlaceGoPiece)
+                            .or_else(in_state(GamePhase::ChooseAction)),
+                    )
+                    .run_if(human_to_move),
             )
             .add_systems(
                 Update,
-                (select_draught, move_draught).run_if(in_state(GamePhase::MoveDraught)),
+                (select_draught, move_draught)
+                    .run_if(
+                        in_state(GamePhase::MoveDraught).or_else(in_state(GamePhase::ChooseAction)),
+                    )
+                    .run_if(human_to_move),
             )
             .add_event::<EventHoverSquare>()
-            .add_event::<EventClickWatchtower>()
             .add_event::<EventClickSquare>()
             .add_event::<EventClickCircle>()
             .add_event::<EventClickDraught>()
+            .add_event::<EventMovePlayed>()
+            .add_event::<EventStepStarted>()
+            .add_systems(
+                Update,
+                play_controllers.run_if(in_state(GameState::Watchtower)),
+            )
+            .init_resource::<Players>()
+            .init_resource::<SearchConfig>()
+            .init_resource::<MctsConfig>()
+            .init_resource::<ResumedGame>()
             .insert_resource(GameLogic::new())
             .insert_resource(SelectedDraught { n: None })
             .insert_resource(ClearColor(Color::BLACK));
@@ -0,0 +1,314 @@
+//! Move suggestions for the side to move.
+//!
+//! The Hint button runs a Monte Carlo search in the background, then marks the best move on the
+//! board and lists the three most promising moves with how often they won their playouts.
+
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::{
+    board::Move,
+    events::*,
+    game::human_to_move,
+    loading::{MaterialAssets, MeshAssets},
+    logic::*,
+    mcts::{MctsConfig, MctsResult, MctsSearch, MoveStats},
+    players::Players,
+    ui::{HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+    GameState,
+};
+
+/// Playouts of a hint search, about a second of thinking.
+const HINT_PLAYOUTS: usize = 2000;
+
+/// Candidates listed next to the board.
+const HINT_MOVES: usize = 3;
+
+#[derive(Component)]
+struct ButtonHint;
+
+#[derive(Component)]
+struct HintText;
+
+/// Highlight of the suggested move on the board.
+#[derive(Component)]
+struct HintMarker;
+
+/// Search for the hint being worked out.
+#[derive(Resource, Default)]
+struct HintSearch(Option<MctsSearch>);
+
+fn init_hint(mut commands: Commands) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(150.0),
+                    width: Val::Px(170.0),
+                    height: Val::Px(65.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            ButtonHint,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    "Hint",
+                    TextStyle {
+                        font_size: 30.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(Pickable::IGNORE);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(225.0),
+                left: Val::Px(10.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                HintText,
+            ));
+        })
+        .insert(Pickable::IGNORE);
+}
+
+fn hint_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ButtonHint>),
+    >,
+    game_logic: Res<GameLogic>,
+    mut hint_search: ResMut<HintSearch>,
+    mut q_text: Query<&mut Text, With<HintText>>,
+) {
+    for (interaction, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if hint_search.0.is_none() && !game_logic.legal_moves().is_empty() {
+                    let config = MctsConfig {
+                        playouts: HINT_PLAYOUTS,
+                        ..default()
+                    };
+                    hint_search.0 = Some(MctsSearch::start(&game_logic, config));
+                    for mut text in q_text.iter_mut() {
+                        text.sections[0].value = "Thinking...".into();
+                    }
+                }
+
+                *color = PRESSED_BUTTON.into();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Moves listed next to the board, the most visited first.
+fn candidates(result: &MctsResult) -> &[MoveStats] {
+    &result.moves[..result.moves.len().min(HINT_MOVES)]
+}
+
+/// Marks the best move once the search is done and lists the best candidates.
+fn show_hint(
+    mut commands: Commands,
+    meshes: Res<MeshAssets>,
+    materials: Res<MaterialAssets>,
+    game_logic: Res<GameLogic>,
+    mut hint_search: ResMut<HintSearch>,
+    mut q_text: Query<&mut Text, With<HintText>>,
+) {
+    if !hint_search
+        .0
+        .as_ref()
+        .is_some_and(|search| search.is_finished())
+    {
+        return;
+    }
+    let search = hint_search.0.take().unwrap();
+    if search.position() != game_logic.hash() {
+        return;
+    }
+    let result = search.finish();
+
+    let mut value = String::from("Hints:");
+    for (n, stats) in candidates(&result).iter().enumerate() {
+        value += &format!("\n{}. {}, wins {:.0}%", n + 1, stats.mv, stats.value * 100.);
+    }
+    for mut text in q_text.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+
+    let Some(best_move) = result.best_move else {
+        return;
+    };
+    let board = game_logic.board();
+    let mut marker = |transform: Transform, mesh: &Handle<Mesh>| {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: materials.green.clone(),
+                transform,
+                ..default()
+            },
+            Name::new("HintMarker"),
+            HintMarker,
+            Pickable::IGNORE,
+        ));
+    };
+    let square =
+        |(i, j): (usize, usize)| Transform::from_translation(Vec3::new(i as f32, 0.002, j as f32));
+
+    match best_move {
+        Move::PlaceStone { i, j } => marker(
+            Transform::from_translation(Vec3::new(i as f32 + 0.5, 0.06, j as f32 + 0.5))
+                .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            &meshes.circle,
+        ),
+        Move::StepDraught { from, to } | Move::TakeOverDraught { from, to } => {
+            marker(square(from), &meshes.square_plane);
+            marker(square(to), &meshes.square_plane);
+        }
+        Move::Nuke { i, j } => {
+            for blasted in board.blast((i, j)).squares.iter() {
+                marker(square(blasted), &meshes.square_plane);
+            }
+        }
+        Move::PlaceWatchtower { i, j } => marker(square((i, j)), &meshes.square_plane),
+        _ => {}
+    }
+}
+
+/// Hints are about one position, any move makes them stale.
+fn clear_hint(
+    mut commands: Commands,
+    mut hint_search: ResMut<HintSearch>,
+    q_markers: Query<Entity, With<HintMarker>>,
+    mut q_text: Query<&mut Text, With<HintText>>,
+) {
+    hint_search.0 = None;
+    for entity in q_markers.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for mut text in q_text.iter_mut() {
+        text.sections[0].value.clear();
+    }
+}
+
+/// The Hint button shows while a player at this machine is to move.
+fn show_hint_button(
+    game_logic: Res<GameLogic>,
+    players: Res<Players>,
+    mut q_button: Query<&mut Visibility, With<ButtonHint>>,
+) {
+    let board = game_logic.board();
+    let visible = players.side(board.turn.into()).is_human()
+        && !matches!(board.phase, GamePhase::Initialize | GamePhase::GameOver);
+    for mut visibility in q_button.iter_mut() {
+        *visibility = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HintSearch>()
+            .add_systems(Startup, init_hint)
+            .add_systems(
+                Update,
+                (
+                    hint_button_system.run_if(human_to_move),
+                    show_hint,
+                    clear_hint.run_if(on_event::<EventMovePlayed>()),
+                    show_hint_button.run_if(
+                        on_event::<EventMovePlayed>().or_else(on_event::<EventStepStarted>()),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Watchtower)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardState;
+
+    #[test]
+    fn suggests_a_legal_move_among_the_best_candidates() {
+        let game = GameLogic::from_board(
+            BoardState::from_ascii(
+                "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . B . .
+
+. . . . . . . . .
+
+. . . . . b . . .
+
+. . . w . . . . .
+
+. . W . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+White to move, MoveDraught",
+            )
+            .unwrap(),
+        );
+        let config = MctsConfig {
+            playouts: 300,
+            trees: Some(1),
+            ..default()
+        };
+        let result = MctsSearch::start(&game, config).finish();
+
+        let best_move = result.best_move.unwrap();
+        assert!(game.legal_moves().contains(&best_move), "{best_move:?}");
+        let candidates = candidates(&result);
+        assert!((1..=HINT_MOVES).contains(&candidates.len()));
+        assert_eq!(candidates[0].mv, best_move);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].visits >= pair[1].visits));
+    }
+}
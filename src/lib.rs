@@ -1,15 +1,33 @@
 #![allow(clippy::type_complexity)]
 
+pub mod ascii;
 mod audio;
+pub mod bitboard;
+pub mod board;
 mod events;
 mod game;
+mod hint;
 mod loading;
-mod logic;
+pub mod logic;
+pub mod mcts;
 mod menu;
+pub mod notation;
+pub mod players;
+pub mod protocol;
+pub mod record;
+pub mod rules;
+pub mod save;
+pub mod search;
+pub mod stats;
+pub mod territory;
+pub mod training;
+pub mod turns;
 mod ui;
+pub mod zobrist;
 
 use crate::audio::InternalAudioPlugin;
 use crate::game::WatchtowerPlugin;
+use crate::hint::HintPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
 
@@ -38,6 +56,7 @@ impl Plugin for GamePlugin {
             MenuPlugin,
             InternalAudioPlugin,
             WatchtowerPlugin,
+            HintPlugin,
             TweeningPlugin,
         ));
 
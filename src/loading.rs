@@ -1,8 +1,8 @@
+use crate::rules::{RuleSet, RuleSetLoader};
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-
 pub struct LoadingPlugin;
 
 impl Plugin for LoadingPlugin {
@@ -10,10 +10,14 @@ impl Plugin for LoadingPlugin {
         info!("here!");
 
         app.add_loading_state(
-            LoadingState::new(GameState::Loading).continue_to_state(GameState::Watchtower),
+            LoadingState::new(GameState::Loading).continue_to_state(GameState::Menu),
         );
         app.add_collection_to_loading_state::<_, AudioAssets>(GameState::Loading);
         app.add_collection_to_loading_state::<_, TextureAssets>(GameState::Loading);
+        app.add_collection_to_loading_state::<_, RuleSetAssets>(GameState::Loading);
+
+        app.init_asset::<RuleSet>()
+            .init_asset_loader::<RuleSetLoader>();
 
         app.init_resource::<MaterialAssets>();
         app.init_resource::<MeshAssets>();
@@ -34,6 +38,19 @@ pub struct TextureAssets {
     pub github: Handle<Image>,
 }
 
+#[derive(AssetCollection, Resource)]
+pub struct RuleSetAssets {
+    #[asset(
+        paths(
+            "rules/classic.rules.ron",
+            "rules/quick.rules.ron",
+            "rules/big_bang.rules.ron"
+        ),
+        collection(typed)
+    )]
+    pub presets: Vec<Handle<RuleSet>>,
+}
+
 #[derive(Resource)]
 pub struct MeshAssets {
     pub square_plane: Handle<Mesh>,
@@ -80,6 +97,7 @@ pub struct MaterialAssets {
     pub yellow: Handle<StandardMaterial>,
     pub blue: Handle<StandardMaterial>,
     pub red: Handle<StandardMaterial>,
+    pub green: Handle<StandardMaterial>,
     pub transparent_white: Handle<StandardMaterial>,
     pub transparent_black: Handle<StandardMaterial>,
 }
@@ -95,6 +113,7 @@ impl FromWorld for MaterialAssets {
             black: materials_asset.add(bevy::prelude::Color::rgb(0., 0.1, 0.1).into()),
             white: materials_asset.add(bevy::prelude::Color::rgb(1., 0.9, 0.9).into()),
             red: materials_asset.add(bevy::prelude::Color::rgba(1., 0.1, 0.1, 0.5).into()),
+            green: materials_asset.add(bevy::prelude::Color::rgba(0.1, 0.9, 0.1, 0.6).into()),
             yellow: materials_asset.add(bevy::prelude::Color::YELLOW.into()),
             blue: materials_asset.add(bevy::prelude::Color::BLUE.into()),
             transparent_white: materials_asset
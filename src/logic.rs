@@ -1,15 +1,47 @@
-use std::collections::HashSet;
-
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitboard::Bitboard,
+    board::*,
+    rules::{Repetition, RuleSet},
+    stats::*,
+    zobrist,
+};
+
+/// Largest board the rules support, smaller ones are cut out of it.
+pub const MAX_BOARD_SIZE: usize = 19;
+
+/// Size of the board picked in the menu. There are `size` x `size` squares for draughts and
+/// `size - 1` x `size - 1` intersections between them for stones.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct BoardConfig {
+    pub size: usize,
+}
 
-pub const BOARD_SIZE: usize = 19;
-
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct GameLogic {
-    log: Vec<(GamePhase, Turn)>,
+    log: Vec<LogEntry>,
+    /// Hashes of every position of the game, the current one last.
+    history: Vec<u64>,
+    board: BoardState,
+    /// Side whose draw offer waits for an answer.
+    draw_offer: Option<Side>,
+    /// Steps passed in a row. Stopping a chain of takeovers doesn't count.
+    passes: usize,
+    ending: Option<Ending>,
+}
+
+/// A move played during the game and everything it changed, destroyed pieces included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    pub phase: GamePhase,
+    pub turn: Turn,
+    pub mv: Move,
+    pub outcome: Outcome,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum Side {
     Black,
     White,
@@ -26,38 +58,89 @@ pub struct Stone {
 pub struct Draught {
     pub i: usize,
     pub j: usize,
-    pub n: i8,
+    pub n: usize,
     pub side: Side,
 }
 
-#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
+#[derive(States, Default, Clone, Copy, Eq, PartialEq, Debug, Hash, Serialize, Deserialize)]
 pub enum GamePhase {
     #[default]
     Initialize,
     PlaceWatchtower,
-    // can't trigger PlaceWatchtower after PlaceWatchtower so there's a workaround
-    TriggerPlaceWatchtower,
     PlaceGoPiece,
     MoveDraught,
+    /// Step of the turn that allows moving a draught or placing a stone.
+    ChooseAction,
     GameOver,
 }
 
-#[derive(Debug)]
-pub struct GameStats {
-    n_moves: usize,
-    white_territory: usize,
-    black_territory: usize,
-    white_draughts: usize,
-    black_draughts: usize,
+/// How a finished game ended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum GameResult {
+    Winner(Side),
+    Draw,
+}
+
+/// Why a game is over.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum Ending {
+    /// A watchtower was taken or both were destroyed.
+    Watchtower,
+    Resignation(Side),
+    /// Both players agreed to a draw.
+    Agreement,
+    Repetition,
+    /// Both players passed whole turns.
+    Passes,
+    /// The move limit was reached.
+    MoveLimit,
+    /// The side to move has no move the repetition rule allows.
+    NoMoves,
 }
 
-#[derive(Resource, Default, Clone, Copy, Debug)]
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug, Hash)]
 pub enum Turn {
     Black,
     #[default]
     White,
 }
 
+impl Side {
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Black => Side::White,
+            Side::White => Side::Black,
+        }
+    }
+}
+
+impl Turn {
+    pub fn next(self) -> Turn {
+        match self {
+            Turn::Black => Turn::White,
+            Turn::White => Turn::Black,
+        }
+    }
+}
+
+impl From<Side> for Turn {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Black => Turn::Black,
+            Side::White => Turn::White,
+        }
+    }
+}
+
+impl From<Turn> for Side {
+    fn from(turn: Turn) -> Self {
+        match turn {
+            Turn::Black => Side::Black,
+            Turn::White => Side::White,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum CheckersMoveType {
     Regular,
@@ -72,1064 +155,355 @@ pub enum GoMoveType {
     TowerTakeOver,
 }
 
-impl GameLogic {
-    pub fn new() -> Self {
-        GameLogic { log: vec![] }
-    }
-
-    pub fn stats(
-        &self,
-        black_draughts: Vec<(usize, usize)>,
-        white_draughts: Vec<(usize, usize)>,
-        white_stones: Vec<(usize, usize)>,
-        black_stones: Vec<(usize, usize)>,
-    ) -> GameStats {
-        let n_moves = self.log.len() - 2;
-        let (white_regions, _) = self.legal_go_moves(
-            Turn::White,
-            black_draughts.clone(),
-            white_draughts.clone(),
-            white_stones.clone(),
-            black_stones.clone(),
-            (0, 0),
-            (0, 0),
-        );
-
-        let (black_regions, _) = self.legal_go_moves(
-            Turn::Black,
-            black_draughts.clone(),
-            white_draughts.clone(),
-            white_stones.clone(),
-            black_stones.clone(),
-            (0, 0),
-            (0, 0),
-        );
-
-        let mut white_territory = 0;
-        for set in white_regions.iter() {
-            white_territory += set.len();
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig {
+            size: MAX_BOARD_SIZE,
         }
+    }
+}
 
-        let mut black_territory = 0;
-        for set in black_regions.iter() {
-            black_territory += set.len();
-        }
+impl BoardConfig {
+    pub const SIZES: [usize; 3] = [9, 13, 19];
 
-        GameStats {
-            n_moves,
-            white_territory,
-            black_territory,
-            white_draughts: white_draughts.len(),
-            black_draughts: black_draughts.len(),
-        }
+    pub fn new(size: usize) -> Self {
+        assert!(Self::supports(size), "unsupported board size {size}");
+        BoardConfig { size }
     }
 
-    pub fn log(&mut self, game_phase: GamePhase, turn: Turn) {
-        self.log.push((game_phase, turn));
+    /// Whether boards of `size` can be played, check sizes read from outside before `new`.
+    pub fn supports(size: usize) -> bool {
+        (9..=MAX_BOARD_SIZE).contains(&size)
     }
 
-    pub fn next_state(&self) -> (GamePhase, Turn) {
-        let (game_phase, turn) = self.log.last().unwrap();
-        match game_phase {
-            GamePhase::PlaceWatchtower => match *turn {
-                Turn::Black => (GamePhase::MoveDraught, Turn::White),
-                Turn::White => (GamePhase::PlaceWatchtower, Turn::Black),
-            },
-            GamePhase::PlaceGoPiece => (
-                GamePhase::MoveDraught,
-                match turn {
-                    Turn::Black => Turn::White,
-                    Turn::White => Turn::Black,
-                },
-            ),
-            GamePhase::MoveDraught => (GamePhase::PlaceGoPiece, *turn),
-            _ => (GamePhase::PlaceWatchtower, *turn),
-        }
+    pub fn squares(&self) -> Bitboard {
+        Bitboard::rect(self.size, self.size)
     }
 
-    pub fn expand_from(
-        &self,
-        start: (usize, usize),
-        region: Vec<(usize, usize)>,
-    ) -> Vec<(usize, usize)> {
-        let mut visited_points: Vec<(usize, usize)> = vec![];
-        let mut stack: Vec<(usize, usize)> = vec![start];
-
-        let mut reached_top = false;
-        let mut reached_bottom = false;
-        let mut reached_left = false;
-        let mut reached_right = false;
-
-        let candidates: Vec<(i8, i8)> = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-        let mut region_ = region.clone();
-
-        // this is a hack but i spent way too much time debugging this
-        let mut hack_elements: Vec<(usize, usize)> = Vec::new();
-        for i in 0..BOARD_SIZE {
-            hack_elements.push((i, BOARD_SIZE - 1));
-        }
-        for i in 0..BOARD_SIZE {
-            hack_elements.push((BOARD_SIZE - 1, i));
-        }
-        for e in hack_elements.clone() {
-            region_.push(e);
-        }
-        // ---
-
-        while let Some((i, j)) = stack.pop() {
-            if visited_points.contains(&(i, j)) {
-                continue;
-            }
-
-            region_.push((i, j));
-            visited_points.push((i, j));
-
-            for c in candidates.clone() {
-                let candidate = (i as i8 + c.0, j as i8 + c.1);
-
-                if candidate.0 < 0
-                    || candidate.1 < 0
-                    || candidate.0 > BOARD_SIZE as i8
-                    || candidate.1 > BOARD_SIZE as i8
-                {
-                    continue;
-                }
-
-                let candidate = (candidate.0 as usize, candidate.1 as usize);
-
-                if candidate.0 == 0 {
-                    reached_left = true;
-                }
-                if candidate.0 == (BOARD_SIZE - 1) {
-                    reached_right = true;
-                }
-                if candidate.1 == 0 {
-                    reached_bottom = true;
-                }
-                if candidate.1 == (BOARD_SIZE - 1) {
-                    reached_top = true;
-                }
+    pub fn intersections(&self) -> Bitboard {
+        Bitboard::rect(self.size - 1, self.size - 1)
+    }
 
-                if !region_.contains(&candidate) {
-                    stack.push(candidate);
-                }
-            }
+    /// Squares between a watchtower and the edge, so its draughts and stones fit on the board
+    /// and small boards still have room for two towers.
+    pub fn watchtower_margin(&self) -> usize {
+        if self.size >= 13 {
+            3
+        } else {
+            2
         }
+    }
 
-        if !(reached_bottom && reached_left && reached_right && reached_top) {
-            return region_
-                .iter()
-                .filter(|e| !hack_elements.clone().contains(e))
-                .copied()
-                .collect();
-        }
+    pub fn fits_watchtower(&self, (i, j): (usize, usize)) -> bool {
+        let margin = self.watchtower_margin();
+        let range = margin..self.size - margin;
+        range.contains(&i) && range.contains(&j)
+    }
 
-        region
+    /// Where a new watchtower shows up before the player moves it.
+    pub fn center(&self) -> (usize, usize) {
+        (self.size / 2 + 1, self.size / 2 + 1)
     }
+}
 
-    pub fn fill_region(&self, region: Vec<(usize, usize)>) -> (Vec<(usize, usize)>, bool) {
-        let mut region = region.clone();
-        let mut is_expanded = false;
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                let expanded_region = self.expand_from((i, j), region.clone());
+impl Default for GameLogic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-                if expanded_region.len() > region.len() {
-                    region = expanded_region;
-                    is_expanded = true;
-                }
-            }
-        }
+impl GameLogic {
+    pub fn new() -> Self {
+        Self::with_rules(BoardConfig::default(), RuleSet::default())
+    }
 
-        (region, is_expanded)
+    pub fn with_rules(config: BoardConfig, rules: RuleSet) -> Self {
+        Self::from_board(BoardState::with_rules(config, rules))
     }
 
-    pub fn find_region(
-        &self,
-        start: (usize, usize),
-        our_stones: Vec<(usize, usize)>,
-        visited: Vec<(usize, usize)>,
-    ) -> (
-        Option<Vec<(usize, usize)>>,
-        Vec<(usize, usize)>,
-        Vec<(usize, usize)>,
-    ) {
-        if !our_stones.contains(&start) {
-            return (None, visited, vec![]);
+    /// Game that goes on from `board`, with nothing played before it.
+    pub fn from_board(board: BoardState) -> Self {
+        GameLogic {
+            log: vec![],
+            history: vec![zobrist::hash(&board)],
+            board,
+            draw_offer: None,
+            passes: 0,
+            ending: None,
         }
+    }
 
-        let mut region: Vec<(usize, usize)> = vec![];
-        let mut stack: Vec<(usize, usize)> = vec![start];
-        let mut visited = visited.clone();
-
-        while let Some((i, j)) = stack.pop() {
-            if visited.contains(&(i, j)) {
-                continue;
-            }
-            visited.push((i, j));
-            region.push((i, j));
-
-            // up
-            let up = (i, j + 1);
-            if up.1 < BOARD_SIZE && our_stones.contains(&up) && !region.contains(&up) {
-                stack.push(up);
-            }
-
-            // down
-            if j >= 1 {
-                let down = (i, j - 1);
-                if our_stones.contains(&down) && !region.contains(&down) {
-                    stack.push(down);
-                }
-            }
-
-            // left
-            if i >= 1 {
-                let left = (i - 1, j);
-                if our_stones.contains(&left) && !region.contains(&left) {
-                    stack.push(left);
-                }
-            }
-
-            // right
-            let right = (i + 1, j);
-            if right.0 < BOARD_SIZE && our_stones.contains(&right) && !region.contains(&right) {
-                stack.push(right);
-            }
-
-            // up-right
-            if i >= 1 {
-                let up_right = (i + 1, j + 1);
-                if up_right.1 < BOARD_SIZE
-                    && up_right.0 < BOARD_SIZE
-                    && our_stones.contains(&up_right)
-                    && !region.contains(&up_right)
-                {
-                    stack.push(up_right);
-                }
-            }
-            // up-left
-            if i >= 1 {
-                let up_left = (i - 1, j + 1);
-                if up_left.1 < BOARD_SIZE
-                    && our_stones.contains(&up_left)
-                    && !region.contains(&up_left)
-                {
-                    stack.push(up_left);
-                }
-            }
-            // down-right
-            if j >= 1 {
-                let down_right = (i + 1, j - 1);
-                if down_right.0 < BOARD_SIZE
-                    && our_stones.contains(&down_right)
-                    && !region.contains(&down_right)
-                {
-                    stack.push(down_right);
-                }
-            }
-            // down-left
-            if j >= 1 && i >= 1 {
-                let down_left = (i - 1, j - 1);
-                if our_stones.contains(&down_left) && !region.contains(&down_left) {
-                    stack.push(down_left);
-                }
-            }
-        }
+    pub fn board(&self) -> &BoardState {
+        &self.board
+    }
 
-        if region.len() < 3 {
-            return (None, visited, vec![]);
+    /// Plays `mv` for the side to move and records it in the log.
+    pub fn apply(&mut self, mv: Move) -> Result<Outcome, MoveError> {
+        if !mv.is_board_move() {
+            return self.settle(mv);
         }
 
-        // fill gaps within region
-
-        let (filled_region, _is_expanded) = self.fill_region(region.clone());
-        // if !is_expanded && region.len() > 4 {
-        //     return (None, visited);
-        // }
+        let (game_phase, turn) = (self.board.phase, self.board.turn);
+        let in_chain = self.board.chain.is_some();
+        let mut board = self.board.clone();
+        let outcome = board.apply(mv)?;
 
-        // remove duplicates
-        let filled_region: HashSet<(usize, usize)> = filled_region
-            .into_iter()
-            .collect::<Vec<(usize, usize)>>()
-            .into_iter()
-            .collect();
-        // convert to vector
-        let mut filled_region: Vec<(usize, usize)> = filled_region.into_iter().collect();
-
-        let stone_annihilate_region = filled_region.clone();
-
-        // clean-up
-        let stone_exists = |set: Vec<(usize, usize)>, (i, j): (usize, usize)| -> bool {
-            set.iter()
-                .filter(|stone| stone.0 == i && stone.1 == j)
-                .count()
-                != 0
+        let hash = zobrist::hash(&board);
+        if self.board.rules.repetition == Repetition::Forbidden
+            && mv != Move::Pass
+            && self.history.contains(&hash)
+        {
+            return Err(MoveError::RepeatedPosition);
+        }
+
+        self.board = board;
+        self.log.push(LogEntry {
+            phase: game_phase,
+            turn,
+            mv,
+            outcome: outcome.clone(),
+        });
+        self.history.push(hash);
+
+        // playing on declines the opponent's offer
+        if self.draw_offer.is_some_and(|side| side != turn.into()) {
+            self.draw_offer = None;
+        }
+        match mv {
+            Move::Pass if in_chain => {}
+            Move::Pass => self.passes += 1,
+            _ => self.passes = 0,
+        }
+
+        self.ending = if self.board.phase == GamePhase::GameOver {
+            Some(Ending::Watchtower)
+        } else if self.is_repetition_draw() {
+            Some(Ending::Repetition)
+        } else if self.is_passed_out() {
+            Some(Ending::Passes)
+        } else if self.moves_left() == Some(0) {
+            Some(Ending::MoveLimit)
+        } else if self.is_stuck() {
+            Some(Ending::NoMoves)
+        } else {
+            None
         };
-
-        let mut clean_region: Vec<(usize, usize)> = Vec::new();
-
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                let stone = (i, j);
-
-                if !stone_exists(filled_region.clone(), stone) {
-                    continue;
-                }
-
-                if i == 0 || i == (BOARD_SIZE - 1) {
-                    clean_region.push(stone);
-                    continue;
-                }
-
-                let next_stone = (i + 1, j);
-                let prev_stone = (i - 1, j);
-
-                let has_next_stone = stone_exists(filled_region.clone(), next_stone);
-                let has_prev_stone = stone_exists(filled_region.clone(), prev_stone);
-                if has_next_stone || has_prev_stone {
-                    clean_region.push(stone);
-                }
-            }
+        if self.ending.is_some() {
+            self.board.phase = GamePhase::GameOver;
         }
 
-        filled_region = clean_region.clone();
-        let mut clean_region: Vec<(usize, usize)> = Vec::new();
-
-        for j in 0..BOARD_SIZE {
-            for i in 0..BOARD_SIZE {
-                let stone = (i, j);
-
-                if !stone_exists(filled_region.clone(), stone) {
-                    continue;
-                }
-
-                if i == 0 || i == (BOARD_SIZE - 1) {
-                    clean_region.push(stone);
-                    continue;
-                }
-
-                let next_stone = (i, j + 1);
-                let prev_stone = (i, j - 1);
-
-                let has_next_stone = stone_exists(filled_region.clone(), next_stone);
-                let has_prev_stone = stone_exists(filled_region.clone(), prev_stone);
-                if has_next_stone || has_prev_stone {
-                    clean_region.push(stone);
-                }
-            }
-        }
-
-        let floor_region: Vec<(i32, i32)> = clean_region
-            .iter()
-            .map(|(i, j)| (*i as f32 + 0.5, *j as f32 + 0.5))
-            .map(|(i, j)| (i.ceil() as i32, j.ceil() as i32))
-            .collect();
-
-        let ceil_region: Vec<(i32, i32)> = clean_region
-            .iter()
-            .map(|(i, j)| (*i as f32 + 0.5, *j as f32 + 0.5))
-            .map(|(i, j)| (i.floor() as i32, j.floor() as i32))
-            .collect();
-
-        let mut clean_region: Vec<(usize, usize)> = Vec::new();
-
-        let stone_exists = |set: Vec<(i32, i32)>, (i, j): (usize, usize)| -> bool {
-            set.iter()
-                .filter(|stone| stone.0 == i as i32 && stone.1 == j as i32)
-                .count()
-                != 0
-        };
-
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                let stone = (i, j);
+        Ok(outcome)
+    }
 
-                if stone_exists(floor_region.clone(), stone)
-                    && stone_exists(ceil_region.clone(), stone)
-                {
-                    clean_region.push(stone);
-                }
-            }
+    /// Resigning and draws, played by the side to move without touching the board.
+    fn settle(&mut self, mv: Move) -> Result<Outcome, MoveError> {
+        if self.board.phase == GamePhase::GameOver {
+            return Err(MoveError::GameOver);
         }
 
-        let stone_exists = |set: Vec<(usize, usize)>, (i, j): (usize, usize)| -> bool {
-            set.iter()
-                .filter(|stone| stone.0 == i && stone.1 == j)
-                .count()
-                != 0
-        };
-
-        let mut super_clean_region: Vec<(usize, usize)> = Vec::new();
-
-        for i in 1..BOARD_SIZE {
-            for j in 1..BOARD_SIZE {
-                let stone = (i, j);
-                let previous_stone = (i - 1, j);
-
-                if !stone_exists(clean_region.clone(), previous_stone)
-                    && stone_exists(clean_region.clone(), stone)
-                {
-                    let stone_1 = (i - 1, j - 1);
-                    let stone_2 = (i - 1, j);
-
-                    if stone_exists(our_stones.clone(), stone_1)
-                        && stone_exists(our_stones.clone(), stone_2)
-                    {
-                        super_clean_region.push(stone);
-                    }
-                } else if stone_exists(clean_region.clone(), stone) {
-                    super_clean_region.push(stone);
+        let side: Side = self.board.turn.into();
+        match mv {
+            Move::Resign => self.ending = Some(Ending::Resignation(side)),
+            Move::OfferDraw => self.draw_offer = Some(side),
+            Move::AcceptDraw => {
+                if self.draw_offer != Some(side.opposite()) {
+                    return Err(MoveError::NoDrawOffer);
                 }
+                self.draw_offer = None;
+                self.ending = Some(Ending::Agreement);
             }
+            _ => unreachable!("{mv:?} is played on the board"),
         }
 
-        let clean_region = super_clean_region.clone();
-        let mut super_clean_region: Vec<(usize, usize)> = Vec::new();
-
-        for i in (0..BOARD_SIZE).rev() {
-            for j in 0..BOARD_SIZE {
-                let stone = (i, j);
-                let previous_stone = (i + 1, j);
-
-                if !stone_exists(clean_region.clone(), previous_stone)
-                    && stone_exists(clean_region.clone(), stone)
-                {
-                    let stone_1 = (i, j - 1);
-                    let stone_2 = (i, j);
-
-                    if stone_exists(our_stones.clone(), stone_1)
-                        && stone_exists(our_stones.clone(), stone_2)
-                    {
-                        super_clean_region.push(stone);
-                    }
-                } else if stone_exists(clean_region.clone(), stone) {
-                    super_clean_region.push(stone);
-                }
-            }
+        // like board moves, the entry has the phase the move was played in
+        let outcome = Outcome::new(side);
+        self.log.push(LogEntry {
+            phase: self.board.phase,
+            turn: self.board.turn,
+            mv,
+            outcome: outcome.clone(),
+        });
+        if self.ending.is_some() {
+            self.board.phase = GamePhase::GameOver;
         }
-
-        (Some(super_clean_region), visited, stone_annihilate_region)
+        Ok(outcome)
     }
 
-    pub fn legal_go_moves(
-        &self,
-        turn: Turn,
-        black_draughts: Vec<(usize, usize)>,
-        white_draughts: Vec<(usize, usize)>,
-        white_stones: Vec<(usize, usize)>,
-        black_stones: Vec<(usize, usize)>,
-        white_tower: (usize, usize),
-        black_tower: (usize, usize),
-    ) -> (Vec<Vec<(usize, usize)>>, Vec<(usize, usize)>) {
-        let (
-            (_our_draughts, our_stones, _our_tower),
-            (_enemy_draughts, _enemy_stones, _enemy_tower),
-        ) = match turn {
-            Turn::Black => (
-                (black_draughts, black_stones, black_tower),
-                (white_draughts, white_stones, white_tower),
-            ),
-            Turn::White => (
-                (white_draughts, white_stones, white_tower),
-                (black_draughts, black_stones, black_tower),
-            ),
-        };
-
-        let mut stone_removal_coords: Vec<(usize, usize)> = Vec::new();
-        let mut convexes = Vec::new();
-        let mut visited: Vec<(usize, usize)> = Vec::new();
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                let (region, visited_, stone_annihite_region) =
-                    self.find_region((i, j), our_stones.clone(), visited.clone());
-
-                if region.is_some() {
-                    convexes.push(region.unwrap());
-                    stone_removal_coords.extend(stone_annihite_region);
-                    visited = visited_;
-                }
-            }
-        }
-
-        stone_removal_coords = stone_removal_coords
-            .into_iter()
-            .collect::<HashSet<(usize, usize)>>()
-            .into_iter()
-            .collect();
-
-        (convexes, stone_removal_coords)
+    pub fn log(&self) -> &[LogEntry] {
+        &self.log
     }
 
-    pub fn legal_draught_moves(
-        &self,
-        turn: Turn,
-        draught: (usize, usize),
-        black_draughts: Vec<(usize, usize)>,
-        white_draughts: Vec<(usize, usize)>,
-        white_stones: Vec<(usize, usize)>,
-        black_stones: Vec<(usize, usize)>,
-        white_tower: (usize, usize),
-        black_tower: (usize, usize),
-    ) -> (
-        Vec<(usize, usize)>,
-        Vec<CheckersMoveType>,
-        Vec<(usize, usize)>,
-        Vec<(usize, usize)>,
-    ) {
-        let (
-            (our_draughts, _our_stones, _our_tower),
-            (enemy_draughts, _enemy_stones, _enemy_tower),
-        ) = match turn {
-            Turn::Black => (
-                (black_draughts.clone(), black_stones.clone(), black_tower),
-                (white_draughts.clone(), white_stones.clone(), white_tower),
-            ),
-            Turn::White => (
-                (white_draughts.clone(), white_stones.clone(), white_tower),
-                (black_draughts.clone(), black_stones.clone(), black_tower),
-            ),
-        };
-
-        let mut legal_moves: Vec<(usize, usize)> = Vec::new();
-        let mut takeovers: Vec<(usize, usize)> = Vec::new();
-        let mut legal_movetypes: Vec<CheckersMoveType> = Vec::new();
-        let mut occupied_squares = our_draughts.clone();
-        occupied_squares.extend(enemy_draughts.clone());
-        // occupied_squares.extend(our_stones);
-        // occupied_squares.extend(enemy_stones);
-
-        let (opposite_stones, stone_removals) = self.legal_go_moves(
-            match turn {
-                Turn::Black => Turn::White,
-                Turn::White => Turn::Black,
-            },
-            black_draughts.clone(),
-            white_draughts.clone(),
-            white_stones.clone(),
-            black_stones.clone(),
-            white_tower,
-            black_tower,
-        );
-
-        let enemy_stones = match turn {
-            Turn::Black => white_stones.clone(),
-            Turn::White => black_stones.clone(),
-        };
-
-        let mut opposite_occupied_squares: Vec<(usize, usize)> = Vec::new();
-        for region in opposite_stones.iter() {
-            opposite_occupied_squares.extend(region);
-        }
-        // opposite_occupied_squares.extend(enemy_stones);
-        // keep only unique elements
-        opposite_occupied_squares = opposite_occupied_squares
+    /// Legal moves of the side to move, without the ones the repetition rule forbids.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.board
+            .legal_moves()
             .into_iter()
-            .collect::<HashSet<(usize, usize)>>()
-            .into_iter()
-            .collect();
-
-        // move up
-        let up = (draught.0, draught.1 + 1);
-        if up.1 < BOARD_SIZE
-            && !occupied_squares.contains(&up)
-            && !opposite_occupied_squares.contains(&up)
-        {
-            legal_moves.push(up);
-            takeovers.push((0, 0));
-            legal_movetypes.push(CheckersMoveType::Regular);
-        }
-
-        // move down
-        if draught.1 >= 1 {
-            let down = (draught.0, draught.1 - 1);
-            if !occupied_squares.contains(&down) && !opposite_occupied_squares.contains(&down) {
-                legal_moves.push(down);
-                takeovers.push((0, 0));
-                legal_movetypes.push(CheckersMoveType::Regular);
-            }
-        }
-
-        // move left
-        if draught.0 >= 1 {
-            let left = (draught.0 - 1, draught.1);
-            if !occupied_squares.contains(&left) && !opposite_occupied_squares.contains(&left) {
-                legal_moves.push(left);
-                takeovers.push((0, 0));
-                legal_movetypes.push(CheckersMoveType::Regular);
-            }
-        }
-
-        // move right
-        let right = (draught.0 + 1, draught.1);
-        if right.0 < BOARD_SIZE
-            && !occupied_squares.contains(&right)
-            && !opposite_occupied_squares.contains(&right)
-        {
-            legal_moves.push(right);
-            takeovers.push((0, 0));
-            legal_movetypes.push(CheckersMoveType::Regular);
-        }
-
-        // move up left
-        if draught.0 >= 1 {
-            let up_left = (draught.0 - 1, draught.1 + 1);
-            if up_left.1 < BOARD_SIZE
-                && !occupied_squares.contains(&up_left)
-                && !opposite_occupied_squares.contains(&up_left)
-            {
-                legal_moves.push(up_left);
-                takeovers.push((0, 0));
-                legal_movetypes.push(CheckersMoveType::Regular);
-            }
-        }
-
-        // move up right
-        let up_right = (draught.0 + 1, draught.1 + 1);
-        if up_right.0 < BOARD_SIZE
-            && up_right.1 < BOARD_SIZE
-            && !occupied_squares.contains(&up_right)
-            && !opposite_occupied_squares.contains(&up_right)
-        {
-            legal_moves.push(up_right);
-            takeovers.push((0, 0));
-            legal_movetypes.push(CheckersMoveType::Regular);
-        }
-
-        // move down left
-        if draught.0 >= 1 && draught.1 >= 1 {
-            let down_left = (draught.0 - 1, draught.1 - 1);
-            if !occupied_squares.contains(&down_left)
-                && !opposite_occupied_squares.contains(&down_left)
-            {
-                legal_moves.push(down_left);
-                takeovers.push((0, 0));
-                legal_movetypes.push(CheckersMoveType::Regular);
-            }
-        }
+            .filter(|mv| !self.repeats_position(*mv))
+            .collect()
+    }
 
-        // move down right
-        if draught.1 >= 1 {
-            let down_right = (draught.0 + 1, draught.1 - 1);
-            if down_right.0 < BOARD_SIZE
-                && !occupied_squares.contains(&down_right)
-                && !opposite_occupied_squares.contains(&down_right)
-            {
-                legal_moves.push(down_right);
-                takeovers.push((0, 0));
-                legal_movetypes.push(CheckersMoveType::Regular);
-            }
+    /// Whether `mv` brings back a position that was already on the board. Passes never count,
+    /// so a game can always end on them.
+    pub fn repeats_position(&self, mv: Move) -> bool {
+        if mv == Move::Pass {
+            return false;
         }
+        let mut board = self.board.clone();
+        board.apply(mv).is_ok() && self.history.contains(&zobrist::hash(&board))
+    }
 
-        // now, takeovers
-        // up
-        let up = (draught.0, draught.1 + 2);
-        let up_takeover = (draught.0, draught.1 + 1);
-        if up.1 < BOARD_SIZE
-            && enemy_draughts.contains(&up_takeover)
-            && !occupied_squares.contains(&up)
-            && !opposite_occupied_squares.contains(&up)
-        {
-            legal_moves.push(up);
-            takeovers.push(up_takeover);
-            legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-        }
+    /// Hash of the current position, equal for equal positions in any game or build.
+    pub fn hash(&self) -> u64 {
+        zobrist::hash(&self.board)
+    }
 
-        // down
-        if draught.1 >= 2 {
-            let down = (draught.0, draught.1 - 2);
-            let down_takeover = (draught.0, draught.1 - 1);
-            if enemy_draughts.contains(&down_takeover)
-                && !occupied_squares.contains(&down)
-                && !opposite_occupied_squares.contains(&down)
-            {
-                legal_moves.push(down);
-                takeovers.push(down_takeover);
-                legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-            }
-        }
+    pub fn history(&self) -> &[u64] {
+        &self.history
+    }
 
-        // left
-        if draught.0 >= 2 {
-            let left = (draught.0 - 2, draught.1);
-            let left_takeover = (draught.0 - 1, draught.1);
-            if enemy_draughts.contains(&left_takeover)
-                && !occupied_squares.contains(&left)
-                && !opposite_occupied_squares.contains(&left)
-            {
-                {
-                    legal_moves.push(left);
-                    takeovers.push(left_takeover);
-                    legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-                }
-            }
-        }
+    /// How many times the last position has been on the board, this time included.
+    pub fn repetitions(&self) -> usize {
+        let last = self.history.last();
+        self.history.iter().filter(|h| Some(*h) == last).count()
+    }
 
-        // right
-        let right = (draught.0 + 2, draught.1);
-        let right_takeover: (usize, usize) = (draught.0 + 1, draught.1);
-        if right.0 < BOARD_SIZE
-            && enemy_draughts.contains(&right_takeover)
-            && !occupied_squares.contains(&right)
-            && !opposite_occupied_squares.contains(&right)
-        {
-            legal_moves.push(right);
-            takeovers.push(right_takeover);
-            legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
+    fn is_repetition_draw(&self) -> bool {
+        match self.board.rules.repetition {
+            Repetition::DrawAfter(n) => self.repetitions() >= n,
+            Repetition::Forbidden => false,
         }
+    }
 
-        // up-right
-        let up_right = (draught.0 + 2, draught.1 + 2);
-        let up_right_takeover = (draught.0 + 1, draught.1 + 1);
-        if up_right.0 < BOARD_SIZE
-            && up_right.1 < BOARD_SIZE
-            && enemy_draughts.contains(&up_right_takeover)
-            && !occupied_squares.contains(&up_right)
-            && !opposite_occupied_squares.contains(&up_right)
-            && !opposite_occupied_squares.contains(&(up_right.0, up_right.1))
-        {
-            legal_moves.push(up_right);
-            takeovers.push(up_right_takeover);
-            legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-        }
+    /// Both players passed every step of a turn, one after the other.
+    fn is_passed_out(&self) -> bool {
+        self.board.rules.end_on_passes
+            && self.passes >= 2 * self.board.rules.turn_structure.n_steps()
+    }
 
-        // up-left
-        if draught.0 >= 2 {
-            let up_left = (draught.0 - 2, draught.1 + 2);
-            let up_left_takeover = (draught.0 - 1, draught.1 + 1);
-            if up_left.1 < BOARD_SIZE
-                && enemy_draughts.contains(&up_left_takeover)
-                && !occupied_squares.contains(&up_left)
-                && !opposite_occupied_squares.contains(&up_left)
-            {
-                legal_moves.push(up_left);
-                takeovers.push(up_left_takeover);
-                legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-            }
-        }
+    /// Side whose draw offer waits for an answer.
+    pub fn draw_offer(&self) -> Option<Side> {
+        self.draw_offer
+    }
 
-        // down-right
-        if draught.1 >= 2 {
-            let down_right = (draught.0 + 2, draught.1 - 2);
-            let down_right_takeover = (draught.0 + 1, draught.1 - 1);
-            if down_right.0 < BOARD_SIZE
-                && enemy_draughts.contains(&down_right_takeover)
-                && !occupied_squares.contains(&down_right)
-                && !opposite_occupied_squares.contains(&down_right)
-            {
-                legal_moves.push(down_right);
-                takeovers.push(down_right_takeover);
-                legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-            }
-        }
+    /// Why the game is over, if it is.
+    pub fn ending(&self) -> Option<Ending> {
+        self.ending
+    }
 
-        // down-left
-        if draught.0 >= 2 && draught.1 >= 2 {
-            let down_left = (draught.0 - 2, draught.1 - 2);
-            let down_left_takeover = (draught.0 - 1, draught.1 - 1);
-            if enemy_draughts.contains(&down_left_takeover)
-                && !occupied_squares.contains(&down_left)
-                && !opposite_occupied_squares.contains(&down_left)
-            {
-                legal_moves.push(down_left);
-                takeovers.push(down_left_takeover);
-                legal_movetypes.push(CheckersMoveType::DraughtTakeOver);
-            }
-        }
+    /// With repetitions forbidden the side to move can run out of moves.
+    fn is_stuck(&self) -> bool {
+        self.board.rules.repetition == Repetition::Forbidden
+            && self.board.phase != GamePhase::GameOver
+            && self.legal_moves().is_empty()
+    }
 
-        let is_two_above = enemy_stones
+    /// Moves played on the board after both watchtowers were placed.
+    pub fn n_moves(&self) -> usize {
+        self.log
             .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1 - 1
-            })
-            .is_some()
-            && enemy_stones
-                .iter()
-                .position(|(i, j)| {
-                    let (i, j) = (*i, *j);
-                    i == draught.0 - 1 && j == draught.1 - 1
-                })
-                .is_some();
-        // remove lower-right
-        if is_two_above {
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+            .filter(|entry| entry.mv.is_board_move())
+            .count()
+            .saturating_sub(2)
+    }
 
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+    /// Moves until the game is adjudicated, if the rules have a move limit.
+    pub fn moves_left(&self) -> Option<usize> {
+        let move_limit = self.board.rules.move_limit?;
+        Some(move_limit.saturating_sub(self.n_moves()))
+    }
 
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+    /// Result of the game once it's over. Resigning loses, an agreed draw is a draw, the last
+    /// standing watchtower wins and repeating a position too often is a draw. Otherwise the
+    /// side with the higher score wins, or it's a draw when scores are level.
+    pub fn result(&self) -> Option<GameResult> {
+        if self.board.phase != GamePhase::GameOver {
+            return None;
         }
 
-        let is_two_below = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1
-            })
-            .is_some()
-            && enemy_stones
-                .iter()
-                .position(|(i, j)| {
-                    let (i, j) = (*i, *j);
-                    i == draught.0 - 1 && j == draught.1
-                })
-                .is_some();
-        // remove lower-right
-        if is_two_below {
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+        match self.ending {
+            Some(Ending::Resignation(side)) => return Some(GameResult::Winner(side.opposite())),
+            Some(Ending::Agreement) => return Some(GameResult::Draw),
+            _ => {}
         }
 
-        let is_two_left = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 - 1
-            })
-            .is_some()
-            && enemy_stones
-                .iter()
-                .position(|(i, j)| {
-                    let (i, j) = (*i, *j);
-                    i == draught.0 - 1 && j == draught.1
-                })
-                .is_some();
-        // remove lower-right
-        if is_two_left {
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+        if let Some(side) = self.board.winner() {
+            return Some(GameResult::Winner(side));
         }
 
-        let is_two_right = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1
-            })
-            .is_some()
-            && enemy_stones
-                .iter()
-                .position(|(i, j)| {
-                    let (i, j) = (*i, *j);
-                    i == draught.0 && j == draught.1 - 1
-                })
-                .is_some();
-        // remove lower-right
-        if is_two_right {
-            println!("is_two_right");
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+        if self.is_repetition_draw() {
+            return Some(GameResult::Draw);
+        }
 
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+        Some(match self.score().leader() {
+            Some(side) => GameResult::Winner(side),
+            None => GameResult::Draw,
+        })
+    }
 
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
+    pub fn stats(&self) -> GameStats {
+        GameStats {
+            n_moves: self.n_moves(),
+            white: self.board.side_stats(Side::White),
+            black: self.board.side_stats(Side::Black),
         }
+    }
 
-        let is_one_above_right = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && (j + 1) == draught.1
-            })
-            .is_some();
-        // remove lower-right
-        if is_one_above_right {
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i - 1 == draught.0 && j + 1 == draught.1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-        }
+    pub fn score(&self) -> Score {
+        self.board.score()
+    }
 
-        let is_one_above_left = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                (i + 1) == draught.0 && (j + 1) == draught.1
-            })
-            .is_some();
-        // remove lower-right
-        if is_one_above_left {
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 - 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
-            }
-        }
+    pub fn next_state(&self) -> (GamePhase, Turn) {
+        (self.board.phase, self.board.turn)
+    }
+}
 
-        let is_one_below_left = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1
-            })
-            .is_some();
-        // remove lower-right
-        if is_one_below_left {
-            println!("is_one_below_left");
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 - 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
+const DIRECTIONS: [(i32, i32); 8] = [
+    (0, 1),
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+/// Squares the draught on `draught` can move to. Draughts can't enter squares occupied by
+/// other draughts or enemy territory, and jumping over an enemy draught takes it over.
+pub fn legal_draught_moves(
+    config: BoardConfig,
+    draught: (usize, usize),
+    our_draughts: Bitboard,
+    enemy_draughts: Bitboard,
+    enemy_territory: Bitboard,
+) -> Vec<((usize, usize), CheckersMoveType)> {
+    let blocked = our_draughts | enemy_draughts | enemy_territory;
+    let square_at = |n: i32, (di, dj): (i32, i32)| {
+        let (i, j) = (draught.0 as i32 + n * di, draught.1 as i32 + n * dj);
+        if i < 0 || j < 0 || i >= config.size as i32 || j >= config.size as i32 {
+            return None;
+        }
+        Some((i as usize, j as usize))
+    };
+
+    let mut legal_moves = Vec::new();
+
+    for direction in DIRECTIONS {
+        if let Some(target) = square_at(1, direction) {
+            if !blocked.contains(target) {
+                legal_moves.push((target, CheckersMoveType::Regular));
             }
         }
+    }
 
-        let is_one_below_right = enemy_stones
-            .iter()
-            .position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 && j == draught.1
-            })
-            .is_some();
-        // remove lower-right
-        if is_one_below_right {
-            println!("is_one_below_right");
-
-            let index = legal_moves.iter().position(|(i, j)| {
-                let (i, j) = (*i, *j);
-                i == draught.0 + 1 && j == draught.1 + 1
-            });
-            if index.is_some() {
-                legal_moves.remove(index.unwrap());
-                takeovers.remove(index.unwrap());
-                legal_movetypes.remove(index.unwrap());
+    // now, takeovers
+    for direction in DIRECTIONS {
+        if let (Some(takeover), Some(target)) = (square_at(1, direction), square_at(2, direction)) {
+            if enemy_draughts.contains(takeover) && !blocked.contains(target) {
+                legal_moves.push((target, CheckersMoveType::DraughtTakeOver));
             }
         }
-
-        // let is_stone_on_draught_place = opposite_occupied_squares.iter().position(|(i, j)| {
-        //     let (i, j) = (*i, *j);
-        //     i == draught.0 && j == draught.1
-        // });
-        // if is_stone_on_draught_place.is_some() {
-        //     let illegal_moves: Vec<(usize, usize)> = vec![(1, 1)];
-        //     for im in illegal_moves.iter() {
-        //         let index = legal_moves.iter().position(|(i, j)| {
-        //             let (i, j) = (*i, *j);
-        //             i == im.0 && j == im.1
-        //         });
-        //         if index.is_some() {
-        //             legal_moves.remove(index.unwrap());
-        //             takeovers.remove(index.unwrap());
-        //             legal_movetypes.remove(index.unwrap());
-        //         }
-        //     }
-        // }
-
-        (legal_moves, legal_movetypes, takeovers, stone_removals)
     }
+
+    legal_moves
 }
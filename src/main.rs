@@ -9,12 +9,17 @@ use bevy::winit::WinitWindows;
 use bevy::DefaultPlugins;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use std::io::Cursor;
+use watchtower_defence::protocol::EngineCommand;
 use watchtower_defence::GamePlugin; // ToDo: Replace bevy_game with your new crate name.
 use winit::window::Icon;
 
 fn main() {
+    // `--engine COMMAND` lets an engine speaking the protocol play a side
+    let engine = std::env::args().skip_while(|arg| arg != "--engine").nth(1);
+
     App::new()
         .insert_resource(Msaa::Sample8)
+        .insert_resource(EngineCommand(engine))
         .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
         .insert_resource(AssetMetaCheck::Never)
         .add_plugins(DefaultPlugins.set(WindowPlugin {
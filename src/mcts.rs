@@ -0,0 +1,397 @@
+//! Monte Carlo tree search for the computer opponent.
+//!
+//! Every tree grows with UCT selection and values new positions by playing them out with
+//! random or heuristic moves. Several trees are grown in parallel on the async compute pool and
+//! their root statistics are added up, the most visited move is played. Playouts that don't
+//! finish the game are scored with the evaluation of the alpha-beta search.
+
+use bevy::prelude::*;
+use bevy::tasks::{block_on, AsyncComputeTaskPool, Task, TaskPool};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::{BoardState, Move},
+    logic::{GameLogic, GamePhase, Side},
+    players::Bot,
+    search::{candidate_moves, evaluate},
+};
+
+/// How moves are picked when a position is played out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rollout {
+    Random,
+    /// Nukes that take the enemy watchtower and takeovers first, random moves otherwise.
+    Heuristic,
+}
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct MctsConfig {
+    /// Playouts for a move, shared by all trees. More playouts play stronger.
+    pub playouts: usize,
+    /// Weight of exploring rarely visited moves against playing good ones.
+    pub exploration: f32,
+    pub rollout: Rollout,
+    /// Steps a playout goes on before the position is evaluated.
+    pub rollout_steps: usize,
+    /// Trees grown in parallel, none for one per thread of the pool.
+    pub trees: Option<usize>,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            playouts: 3000,
+            exploration: 1.4,
+            rollout: Rollout::Heuristic,
+            rollout_steps: 30,
+            trees: None,
+        }
+    }
+}
+
+/// Visits and value of a move at the root.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveStats {
+    pub mv: Move,
+    pub visits: u32,
+    /// Average result of the playouts through the move for the side to move, 1 is a win.
+    pub value: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MctsResult {
+    pub best_move: Option<Move>,
+    /// Moves of the side to move, the most visited first.
+    pub moves: Vec<MoveStats>,
+    pub playouts: usize,
+}
+
+struct Node {
+    mv: Option<Move>,
+    /// Side that played `mv`, the value is counted for it.
+    mover: Side,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<Move>,
+    visits: u32,
+    value: f32,
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+    rng: StdRng,
+    config: MctsConfig,
+}
+
+/// Result of a playout for White: 1 is a win, 0 a loss. Unfinished games are squashed from the
+/// evaluation.
+fn white_result(board: &BoardState) -> f32 {
+    let score = evaluate(board, Side::White) as f32;
+    if board.phase == GamePhase::GameOver {
+        return 0.5 + score.signum() / 2.;
+    }
+    1. / (1. + (-score / 20.).exp())
+}
+
+fn rollout_move(board: &BoardState, moves: &[Move], rng: &mut StdRng, rollout: Rollout) -> Move {
+    if rollout == Rollout::Heuristic {
+        let side: Side = board.turn.into();
+        let enemy_watchtower = board.army(side.opposite()).watchtower;
+        let own_watchtower = board.army(side).watchtower;
+        let winning_nuke = moves.iter().find(|mv| match mv {
+            Move::Nuke { i, j } => {
+                let blast = board.blast((*i, *j)).squares;
+                enemy_watchtower.is_some_and(|w| blast.contains(w))
+                    && !own_watchtower.is_some_and(|w| blast.contains(w))
+            }
+            _ => false,
+        });
+        if let Some(mv) = winning_nuke {
+            return *mv;
+        }
+
+        let takeovers: Vec<&Move> = moves
+            .iter()
+            .filter(|mv| matches!(mv, Move::TakeOverDraught { .. }))
+            .collect();
+        if let Some(mv) = takeovers.choose(rng) {
+            return **mv;
+        }
+
+        // nukes blow up our own pieces too, only the ones above are worth it
+        let quiet: Vec<&Move> = moves
+            .iter()
+            .filter(|mv| !matches!(mv, Move::Nuke { .. } | Move::Pass))
+            .collect();
+        if let Some(mv) = quiet.choose(rng) {
+            return **mv;
+        }
+    }
+
+    *moves.choose(rng).unwrap_or(&Move::Pass)
+}
+
+impl Tree {
+    fn new(root: &GameLogic, config: MctsConfig) -> Self {
+        let mut rng = StdRng::from_entropy();
+        // the repetition rule only counts at the root
+        let legal = root.legal_moves();
+        let mut untried: Vec<Move> = candidate_moves(root.board())
+            .into_iter()
+            .filter(|mv| legal.contains(mv))
+            .collect();
+        if untried.is_empty() {
+            untried = legal;
+        }
+        untried.shuffle(&mut rng);
+
+        Tree {
+            nodes: vec![Node {
+                mv: None,
+                mover: root.board().turn.next().into(),
+                parent: None,
+                children: vec![],
+                untried,
+                visits: 0,
+                value: 0.,
+            }],
+            rng,
+            config,
+        }
+    }
+
+    /// Child of `node` with the highest upper confidence bound.
+    fn select(&self, node: usize) -> usize {
+        let ln_visits = (self.nodes[node].visits.max(1) as f32).ln();
+        let uct = |child: &Node| {
+            let visits = child.visits.max(1) as f32;
+            child.value / visits + self.config.exploration * (ln_visits / visits).sqrt()
+        };
+        *self.nodes[node]
+            .children
+            .iter()
+            .max_by(|a, b| uct(&self.nodes[**a]).total_cmp(&uct(&self.nodes[**b])))
+            .unwrap()
+    }
+
+    fn playout(&mut self, root: &BoardState) {
+        let mut board = root.clone();
+        let mut node = 0;
+
+        // selection
+        while self.nodes[node].untried.is_empty() && !self.nodes[node].children.is_empty() {
+            node = self.select(node);
+            let mv = self.nodes[node].mv.unwrap();
+            if board.apply(mv).is_err() {
+                break;
+            }
+        }
+
+        // expansion
+        if let Some(mv) = self.nodes[node].untried.pop() {
+            let mover: Side = board.turn.into();
+            if board.apply(mv).is_ok() {
+                let mut untried = if board.phase == GamePhase::GameOver {
+                    vec![]
+                } else {
+                    candidate_moves(&board)
+                };
+                untried.shuffle(&mut self.rng);
+
+                self.nodes.push(Node {
+                    mv: Some(mv),
+                    mover,
+                    parent: Some(node),
+                    children: vec![],
+                    untried,
+                    visits: 0,
+                    value: 0.,
+                });
+                let child = self.nodes.len() - 1;
+                self.nodes[node].children.push(child);
+                node = child;
+            }
+        }
+
+        // simulation
+        for _ in 0..self.config.rollout_steps {
+            if board.phase == GamePhase::GameOver {
+                break;
+            }
+            let moves = board.legal_moves();
+            let mv = rollout_move(&board, &moves, &mut self.rng, self.config.rollout);
+            if board.apply(mv).is_err() {
+                break;
+            }
+        }
+
+        // backpropagation
+        let white = white_result(&board);
+        let mut node = Some(node);
+        while let Some(n) = node {
+            let node_ = &mut self.nodes[n];
+            node_.visits += 1;
+            node_.value += match node_.mover {
+                Side::White => white,
+                Side::Black => 1. - white,
+            };
+            node = node_.parent;
+        }
+    }
+
+    fn root_stats(&self) -> Vec<MoveStats> {
+        self.nodes[0]
+            .children
+            .iter()
+            .map(|child| {
+                let child = &self.nodes[*child];
+                MoveStats {
+                    mv: child.mv.unwrap(),
+                    visits: child.visits,
+                    value: child.value,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Trees growing for a position in the background, each with its share of the playouts.
+pub struct MctsSearch {
+    /// Hash of the position searched.
+    position: u64,
+    playouts: usize,
+    trees: Vec<Task<Vec<MoveStats>>>,
+}
+
+impl MctsSearch {
+    /// Starts growing the trees for the side to move in `game`.
+    pub fn start(game: &GameLogic, config: MctsConfig) -> Self {
+        let pool = AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let n_trees = config.trees.unwrap_or(pool.thread_num()).max(1);
+
+        let trees = (0..n_trees)
+            .map(|n| {
+                let game = game.clone();
+                // the first trees make up for playouts that don't divide evenly
+                let playouts =
+                    config.playouts / n_trees + usize::from(n < config.playouts % n_trees);
+                pool.spawn(async move {
+                    let mut tree = Tree::new(&game, config);
+                    for _ in 0..playouts {
+                        tree.playout(game.board());
+                    }
+                    tree.root_stats()
+                })
+            })
+            .collect();
+
+        MctsSearch {
+            position: game.hash(),
+            playouts: config.playouts,
+            trees,
+        }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.trees.iter().all(|tree| tree.is_finished())
+    }
+
+    /// Waits for the trees and adds up their root statistics.
+    pub fn finish(self) -> MctsResult {
+        let trees = self.trees.into_iter().map(block_on).collect();
+        merge(trees, self.playouts)
+    }
+}
+
+/// Adds up the root statistics of the trees, values come in summed and go out averaged.
+fn merge(trees: Vec<Vec<MoveStats>>, playouts: usize) -> MctsResult {
+    let mut moves: Vec<MoveStats> = vec![];
+    for stats in trees.into_iter().flatten() {
+        match moves.iter_mut().find(|merged| merged.mv == stats.mv) {
+            Some(merged) => {
+                merged.visits += stats.visits;
+                merged.value += stats.value;
+            }
+            None => moves.push(stats),
+        }
+    }
+    for stats in moves.iter_mut() {
+        stats.value /= stats.visits.max(1) as f32;
+    }
+    moves.sort_by_key(|stats| std::cmp::Reverse(stats.visits));
+
+    MctsResult {
+        best_move: moves.first().map(|stats| stats.mv),
+        moves,
+        playouts,
+    }
+}
+
+/// Best move for the side to move in `game`, waiting for the trees to grow.
+pub fn search(game: &GameLogic, config: &MctsConfig) -> MctsResult {
+    let legal_moves = game.legal_moves();
+    if legal_moves.len() < 2 {
+        return MctsResult {
+            best_move: legal_moves.first().copied(),
+            moves: vec![],
+            playouts: 0,
+        };
+    }
+
+    MctsSearch::start(game, *config).finish()
+}
+
+/// Bot playing the move Monte Carlo tree search visits most.
+pub struct MctsBot {
+    pub config: MctsConfig,
+    search: Option<MctsSearch>,
+}
+
+impl MctsBot {
+    pub fn new(config: MctsConfig) -> Self {
+        MctsBot {
+            config,
+            search: None,
+        }
+    }
+}
+
+impl Bot for MctsBot {
+    fn name(&self) -> &str {
+        "MCTS"
+    }
+
+    fn poll(&mut self, game: &GameLogic) -> Option<Move> {
+        match &self.search {
+            Some(search) if search.position() == game.hash() => {
+                if !search.is_finished() {
+                    return None;
+                }
+            }
+            // dropping the trees of another position cancels them
+            _ => {
+                let legal_moves = game.legal_moves();
+                if legal_moves.len() < 2 {
+                    self.search = None;
+                    return legal_moves.first().copied();
+                }
+                self.search = Some(MctsSearch::start(game, self.config));
+                return None;
+            }
+        }
+
+        let result = self.search.take()?.finish();
+        if let Some(best) = result.moves.first() {
+            info!(
+                "{} playouts, best move visited {} times, value {:.2}",
+                result.playouts, best.visits, best.value
+            );
+        }
+        result.best_move
+    }
+}
@@ -1,8 +1,16 @@
 use std::time::Duration;
 
-use crate::loading::{MaterialAssets, MeshAssets, TextureAssets};
+use crate::loading::{MaterialAssets, MeshAssets, RuleSetAssets, TextureAssets};
+use crate::logic::{BoardConfig, GameLogic, Side};
+use crate::mcts::{MctsBot, MctsConfig};
+use crate::players::{PlayerController, Players, RandomBot};
+use crate::protocol::{EngineCommand, SubprocessBot};
+use crate::rules::RuleSet;
+use crate::save::{save_path, ResumedGame, SavedGame};
+use crate::search::{AlphaBetaBot, SearchConfig};
 use crate::GameState;
 use bevy::prelude::*;
+use bevy::tasks::{block_on, IoTaskPool, Task};
 use bevy_tweening::lens::TransformPositionLens;
 use bevy_tweening::*;
 use rand::Rng;
@@ -13,17 +21,42 @@ pub struct MenuPlugin;
 /// The menu is only drawn during the State `GameState::Menu` and is removed when that state is exited
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnExit(GameState::Menu),
-            |mut commands: Commands, q_menu_components: Query<(Entity, &MenuComponent)>| {
-                for (e, _) in q_menu_components.iter() {
-                    commands.entity(e).despawn_recursive();
-                }
-            },
-        )
-        .add_systems(OnEnter(GameState::Menu), (setup_menu, spawn_board))
-        .add_systems(Update, click_play_button.run_if(in_state(GameState::Menu)))
-        .add_systems(OnExit(GameState::Menu), cleanup_menu);
+        app.init_resource::<EngineCommand>()
+            .init_resource::<ContinuingGame>()
+            .add_systems(
+                OnExit(GameState::Menu),
+                |mut commands: Commands, q_menu_components: Query<(Entity, &MenuComponent)>| {
+                    for (e, _) in q_menu_components.iter() {
+                        commands.entity(e).despawn_recursive();
+                    }
+                },
+            )
+            .add_systems(OnEnter(GameState::Menu), (setup_menu, spawn_board))
+            .add_systems(
+                Update,
+                (click_play_button, start_continued_game).run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(
+                Update,
+                (despawn_board, spawn_board)
+                    .chain()
+                    .run_if(in_state(GameState::Menu))
+                    .run_if(resource_changed::<BoardConfig>())
+                    .run_if(not(resource_added::<BoardConfig>())),
+            )
+            .add_systems(
+                Update,
+                select_menu_options
+                    .run_if(in_state(GameState::Menu))
+                    .run_if(
+                        resource_changed::<BoardConfig>()
+                            .or_else(resource_changed::<RuleSet>())
+                            .or_else(resource_changed::<Players>())
+                            .or_else(resource_changed::<SearchConfig>())
+                            .or_else(resource_changed::<MctsConfig>()),
+                    ),
+            )
+            .add_systems(OnExit(GameState::Menu), cleanup_menu);
     }
 }
 
@@ -48,9 +81,16 @@ struct MenuComponent;
 #[derive(Component)]
 struct MenuGamePiece;
 
-const BOARD_SIZE: usize = 257;
+/// Backdrop is this many game boards wide.
+const BACKDROP_BOARDS: usize = 13;
 
-fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: Res<MeshAssets>) {
+fn spawn_board(
+    mut commands: Commands,
+    materials: Res<MaterialAssets>,
+    meshes: Res<MeshAssets>,
+    board_config: Res<BoardConfig>,
+) {
+    let backdrop_size = board_config.size * BACKDROP_BOARDS;
     let mut rng = rand::thread_rng();
     // Light
     const N_LIGHTS: usize = 15;
@@ -65,14 +105,14 @@ fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: R
                 Duration::from_secs(duration as u64),
                 TransformPositionLens {
                     start: Vec3::new(
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (i as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (i as f32),
                         start_light as f32,
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (j as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (j as f32),
                     ),
                     end: Vec3::new(
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (i as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (i as f32),
                         end_light as f32,
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (j as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (j as f32),
                     ),
                 },
             )
@@ -87,9 +127,9 @@ fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: R
                         ..Default::default()
                     },
                     transform: Transform::from_xyz(
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (i as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (i as f32),
                         start_light as f32,
-                        (BOARD_SIZE as f32 / N_LIGHTS as f32) * (j as f32),
+                        (backdrop_size as f32 / N_LIGHTS as f32) * (j as f32),
                     ),
                     ..Default::default()
                 },
@@ -100,11 +140,18 @@ fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: R
     }
 
     let camera_transform = Transform::from_translation(Vec3::new(
-        (BOARD_SIZE as f32) / 2.,
-        (BOARD_SIZE as f32) / 4.,
-        (BOARD_SIZE as f32) / 2.,
+        (backdrop_size as f32) / 2.,
+        (backdrop_size as f32) / 4.,
+        (backdrop_size as f32) / 2.,
     ))
-    .looking_at(Vec3::new(94., 0.0, 121.), Vec3::Y);
+    .looking_at(
+        Vec3::new(
+            backdrop_size as f32 * 0.366,
+            0.0,
+            backdrop_size as f32 * 0.471,
+        ),
+        Vec3::Y,
+    );
 
     commands.spawn((
         Camera3dBundle {
@@ -116,9 +163,9 @@ fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: R
     ));
 
     // spawn checkerboard
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            let n = i * BOARD_SIZE + j;
+    for i in 0..backdrop_size {
+        for j in 0..backdrop_size {
+            let n = i * backdrop_size + j;
             let material = match n % 2 {
                 0 => materials.black.clone(),
                 _ => materials.white.clone(),
@@ -212,12 +259,36 @@ fn spawn_board(mut commands: Commands, materials: Res<MaterialAssets>, meshes: R
     }
 }
 
+fn despawn_board(mut commands: Commands, q_menu_components: Query<Entity, With<MenuComponent>>) {
+    for e in q_menu_components.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
 #[derive(Component)]
 struct Menu;
 
-fn setup_menu(mut commands: Commands, textures: Res<TextureAssets>) {
+fn setup_menu(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    presets: Res<RuleSetAssets>,
+    rule_sets: Res<Assets<RuleSet>>,
+    engine: Res<EngineCommand>,
+) {
     info!("menu");
 
+    // a game left unfinished can be continued
+    let saved_game =
+        save_path()
+            .filter(|path| path.exists())
+            .and_then(|path| match SavedGame::read(&path) {
+                Ok(saved_game) => Some(saved_game),
+                Err(err) => {
+                    warn!("can't continue game from {}: {err}", path.display());
+                    None
+                }
+            });
+
     commands
         .spawn((
             NodeBundle {
@@ -261,6 +332,123 @@ fn setup_menu(mut commands: Commands, textures: Res<TextureAssets>) {
                         },
                     ));
                 });
+
+            if let Some(saved_game) = saved_game {
+                let button_colors = ButtonColors::default();
+                children
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(140.0),
+                                height: Val::Px(50.0),
+                                margin: UiRect::top(Val::Px(10.)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: button_colors.normal.into(),
+                            ..Default::default()
+                        },
+                        button_colors,
+                        ContinueGame(saved_game),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Continue",
+                            TextStyle {
+                                font_size: 30.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+
+            // board size, rules and who plays each side
+            let options = [
+                BoardConfig::SIZES
+                    .iter()
+                    .map(|size| (MenuOption::BoardSize(*size), format!("{size}x{size}")))
+                    .collect::<Vec<_>>(),
+                presets
+                    .presets
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(n, handle)| {
+                        Some((MenuOption::RuleSet(n), rule_sets.get(handle)?.name.clone()))
+                    })
+                    .collect::<Vec<_>>(),
+                PlayerKind::ALL
+                    .iter()
+                    .filter(|kind| **kind != PlayerKind::Engine || engine.0.is_some())
+                    .map(|kind| {
+                        (
+                            MenuOption::Player(Side::White, *kind),
+                            format!("White {}", kind.name()),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                PlayerKind::ALL
+                    .iter()
+                    .filter(|kind| **kind != PlayerKind::Engine || engine.0.is_some())
+                    .map(|kind| {
+                        (
+                            MenuOption::Player(Side::Black, *kind),
+                            format!("Black {}", kind.name()),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                BOT_LEVELS
+                    .iter()
+                    .enumerate()
+                    .map(|(n, level)| (MenuOption::BotLevel(n), level.name.to_string()))
+                    .collect::<Vec<_>>(),
+            ];
+
+            for row in options {
+                children
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            margin: UiRect::top(Val::Px(10.)),
+                            column_gap: Val::Px(10.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for (option, label) in row {
+                            let button_colors = ButtonColors::default();
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            min_width: Val::Px(60.0),
+                                            height: Val::Px(30.0),
+                                            padding: UiRect::horizontal(Val::Px(5.)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        background_color: button_colors.normal.into(),
+                                        ..Default::default()
+                                    },
+                                    button_colors,
+                                    option,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        label,
+                                        TextStyle {
+                                            font_size: 20.0,
+                                            color: Color::rgb(0.9, 0.9, 0.9),
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+            }
         });
     commands
         .spawn((
@@ -364,36 +552,345 @@ struct ChangeState(GameState);
 #[derive(Component)]
 struct OpenLink(&'static str);
 
+/// Continues the saved game with its board, rules and players.
+#[derive(Component)]
+struct ContinueGame(SavedGame);
+
+/// Game setting picked with a menu button.
+#[derive(Component, Clone, Copy, PartialEq)]
+enum MenuOption {
+    BoardSize(usize),
+    /// Index of the preset in `RuleSetAssets`.
+    RuleSet(usize),
+    Player(Side, PlayerKind),
+    /// Index of the level in `BOT_LEVELS`.
+    BotLevel(usize),
+}
+
+/// How hard the bots play: how long alpha-beta searches and how many playouts MCTS runs.
+struct BotLevel {
+    name: &'static str,
+    seconds: u64,
+    playouts: usize,
+}
+
+const BOT_LEVELS: [BotLevel; 3] = [
+    BotLevel {
+        name: "Easy",
+        seconds: 1,
+        playouts: 500,
+    },
+    BotLevel {
+        name: "Medium",
+        seconds: 3,
+        playouts: 3000,
+    },
+    BotLevel {
+        name: "Hard",
+        seconds: 10,
+        playouts: 12000,
+    },
+];
+
+/// Time an engine gets for all its moves in a game.
+const ENGINE_CLOCK: Duration = Duration::from_secs(600);
+
+/// Who can play a side, as picked in the menu.
+#[derive(Clone, Copy, PartialEq)]
+enum PlayerKind {
+    Human,
+    Random,
+    AlphaBeta,
+    Mcts,
+    /// Engine started with `--engine`, talking over the protocol.
+    Engine,
+}
+
+impl PlayerKind {
+    const ALL: [PlayerKind; 5] = [
+        PlayerKind::Human,
+        PlayerKind::Random,
+        PlayerKind::AlphaBeta,
+        PlayerKind::Mcts,
+        PlayerKind::Engine,
+    ];
+
+    /// Same as the name of the controller, but for engines which go by their own name.
+    fn name(self) -> &'static str {
+        match self {
+            PlayerKind::Human => "Human",
+            PlayerKind::Random => "Random",
+            PlayerKind::AlphaBeta => "Alpha-Beta",
+            PlayerKind::Mcts => "MCTS",
+            PlayerKind::Engine => "Engine",
+        }
+    }
+
+    /// Kind of the built-in player named `name`. Engines go by their own names and remote
+    /// players can't be told apart by name, so there is none for them.
+    fn from_name(name: &str) -> Option<PlayerKind> {
+        PlayerKind::ALL
+            .into_iter()
+            .filter(|kind| *kind != PlayerKind::Engine)
+            .find(|kind| kind.name() == name)
+    }
+
+    /// Kind of a player picked in the menu, the only bots besides the built-in ones are engines.
+    /// Remote players aren't picked there.
+    fn of(controller: &PlayerController) -> Option<PlayerKind> {
+        match controller {
+            PlayerController::Human => Some(PlayerKind::Human),
+            PlayerController::Bot(bot) => {
+                PlayerKind::from_name(bot.name()).or(Some(PlayerKind::Engine))
+            }
+            PlayerController::Remote(_) => None,
+        }
+    }
+
+    fn controller(
+        self,
+        search_config: SearchConfig,
+        mcts_config: MctsConfig,
+        engine: &EngineCommand,
+    ) -> PlayerController {
+        match self {
+            PlayerKind::Human => PlayerController::Human,
+            PlayerKind::Random => PlayerController::Bot(Box::new(RandomBot::new())),
+            PlayerKind::AlphaBeta => {
+                PlayerController::Bot(Box::new(AlphaBetaBot::new(search_config)))
+            }
+            PlayerKind::Mcts => PlayerController::Bot(Box::new(MctsBot::new(mcts_config))),
+            PlayerKind::Engine => {
+                let command = engine.0.as_deref().unwrap_or_default();
+                match SubprocessBot::spawn(command, ENGINE_CLOCK) {
+                    Ok(bot) => PlayerController::Bot(Box::new(bot)),
+                    Err(err) => {
+                        warn!("can't start engine {command}: {err}");
+                        PlayerController::Human
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Player of a saved game named `name`. Engines have to be the one started with `--engine`,
+/// and remote players can't be reconnected. Engines are started and greeted on a task, which
+/// can take a while.
+fn resumed_player(
+    name: &str,
+    search_config: SearchConfig,
+    mcts_config: MctsConfig,
+    engine: &EngineCommand,
+) -> Task<Result<PlayerController, String>> {
+    if let Some(kind) = PlayerKind::from_name(name) {
+        let player = kind.controller(search_config, mcts_config, engine);
+        return IoTaskPool::get().spawn(async move { Ok(player) });
+    }
+    let name = name.to_owned();
+    let command = engine.0.clone();
+    IoTaskPool::get().spawn(async move {
+        if name == "Remote" {
+            return Err("remote players can't be reconnected".into());
+        }
+        let Some(command) = command else {
+            return Err(format!("{name} was an engine, start it with --engine"));
+        };
+        let mut bot = SubprocessBot::spawn(&command, ENGINE_CLOCK)
+            .map_err(|err| format!("can't start engine {command}: {err}"))?;
+        bot.wait_ready()
+            .map_err(|err| format!("can't start engine {command}: {err}"))?;
+        if bot.id() != name {
+            return Err(format!("game was played by {name}, not {}", bot.id()));
+        }
+        Ok(PlayerController::Bot(Box::new(bot)))
+    })
+}
+
+/// Saved game picked with "Continue", and its players while they are started.
+struct PendingGame {
+    game: GameLogic,
+    white: Task<Result<PlayerController, String>>,
+    black: Task<Result<PlayerController, String>>,
+}
+
+#[derive(Resource, Default)]
+struct ContinuingGame(Option<PendingGame>);
+
+/// Goes on with a saved game once both players are started, polled like a bot's search so the
+/// menu keeps running while an engine answers the handshake.
+fn start_continued_game(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut board_config: ResMut<BoardConfig>,
+    mut rules: ResMut<RuleSet>,
+    mut players: ResMut<Players>,
+    mut continuing_game: ResMut<ContinuingGame>,
+    mut resumed_game: ResMut<ResumedGame>,
+) {
+    let started = continuing_game
+        .0
+        .as_ref()
+        .is_some_and(|pending| pending.white.is_finished() && pending.black.is_finished());
+    if !started {
+        return;
+    }
+    let PendingGame { game, white, black } = continuing_game.0.take().unwrap();
+    let (white, black) = match (block_on(white), block_on(black)) {
+        (Ok(white), Ok(black)) => (white, black),
+        (Err(err), _) | (_, Err(err)) => {
+            warn!("can't continue game: {err}");
+            return;
+        }
+    };
+    // the replayed game has a board and rules that can be played
+    *board_config = game.board().config;
+    *rules = game.board().rules.clone();
+    *players.side_mut(Side::White) = white;
+    *players.side_mut(Side::Black) = black;
+    info!("continuing game after {} moves", game.n_moves());
+    resumed_game.0 = Some(game);
+    next_state.set(GameState::Watchtower);
+}
+
+#[derive(Component)]
+struct Selected;
+
+#[allow(clippy::too_many_arguments)]
 fn click_play_button(
     mut next_state: ResMut<NextState<GameState>>,
+    mut board_config: ResMut<BoardConfig>,
+    mut rules: ResMut<RuleSet>,
+    mut players: ResMut<Players>,
+    mut search_config: ResMut<SearchConfig>,
+    mut mcts_config: ResMut<MctsConfig>,
+    mut continuing_game: ResMut<ContinuingGame>,
+    presets: Res<RuleSetAssets>,
+    rule_sets: Res<Assets<RuleSet>>,
+    engine: Res<EngineCommand>,
     mut interaction_query: Query<
         (
             &Interaction,
             &mut BackgroundColor,
             &ButtonColors,
             Option<&ChangeState>,
+            Option<&ContinueGame>,
             Option<&OpenLink>,
+            Option<&MenuOption>,
+            Option<&Selected>,
         ),
         (Changed<Interaction>, With<Button>),
     >,
 ) {
-    for (interaction, mut color, button_colors, change_state, open_link) in &mut interaction_query {
+    for (
+        interaction,
+        mut color,
+        button_colors,
+        change_state,
+        continue_game,
+        open_link,
+        option,
+        selected,
+    ) in &mut interaction_query
+    {
         match *interaction {
             Interaction::Pressed => {
                 if let Some(state) = change_state {
                     next_state.set(state.0.clone());
+                } else if let Some(ContinueGame(saved_game)) = continue_game {
+                    if continuing_game.0.is_some() {
+                        continue;
+                    }
+                    let game = match saved_game.resume() {
+                        Ok(game) => game,
+                        Err(err) => {
+                            warn!("can't continue game: {err}");
+                            continue;
+                        }
+                    };
+                    let record = &saved_game.record;
+                    let resumed =
+                        |name: &str| resumed_player(name, *search_config, *mcts_config, &engine);
+                    continuing_game.0 = Some(PendingGame {
+                        game,
+                        white: resumed(&record.white),
+                        black: resumed(&record.black),
+                    });
                 } else if let Some(link) = open_link {
                     if let Err(error) = webbrowser::open(link.0) {
                         warn!("Failed to open link {error:?}");
                     }
+                } else if let Some(MenuOption::BoardSize(size)) = option {
+                    if board_config.size != *size {
+                        *board_config = BoardConfig::new(*size);
+                    }
+                } else if let Some(MenuOption::RuleSet(n)) = option {
+                    if let Some(preset) = rule_sets.get(&presets.presets[*n]) {
+                        info!("playing with {} rules", preset.name);
+                        *rules = preset.clone();
+                    }
+                } else if let Some(MenuOption::Player(side, kind)) = option {
+                    *players.side_mut(*side) =
+                        kind.controller(*search_config, *mcts_config, &engine);
+                } else if let Some(MenuOption::BotLevel(n)) = option {
+                    search_config.time_limit = Duration::from_secs(BOT_LEVELS[*n].seconds);
+                    mcts_config.playouts = BOT_LEVELS[*n].playouts;
+                    // bots already picked play at the new level too
+                    for side in [Side::White, Side::Black] {
+                        let kind = [PlayerKind::AlphaBeta, PlayerKind::Mcts]
+                            .into_iter()
+                            .find(|kind| PlayerKind::of(players.side(side)) == Some(*kind));
+                        if let Some(kind) = kind {
+                            *players.side_mut(side) =
+                                kind.controller(*search_config, *mcts_config, &engine);
+                        }
+                    }
                 }
             }
             Interaction::Hovered => {
                 *color = button_colors.hovered.into();
             }
             Interaction::None => {
-                *color = button_colors.normal.into();
+                *color = if selected.is_some() {
+                    button_colors.hovered.into()
+                } else {
+                    button_colors.normal.into()
+                };
+            }
+        }
+    }
+}
+
+/// Highlights buttons of the board size, the rules and the players the game will be played
+/// with.
+#[allow(clippy::too_many_arguments)]
+fn select_menu_options(
+    mut commands: Commands,
+    board_config: Res<BoardConfig>,
+    rules: Res<RuleSet>,
+    players: Res<Players>,
+    search_config: Res<SearchConfig>,
+    mcts_config: Res<MctsConfig>,
+    presets: Res<RuleSetAssets>,
+    rule_sets: Res<Assets<RuleSet>>,
+    mut q_options: Query<(Entity, &MenuOption, &ButtonColors, &mut BackgroundColor)>,
+) {
+    for (entity, option, button_colors, mut color) in q_options.iter_mut() {
+        let selected = match option {
+            MenuOption::BoardSize(size) => *size == board_config.size,
+            MenuOption::RuleSet(n) => rule_sets.get(&presets.presets[*n]) == Some(&*rules),
+            MenuOption::Player(side, kind) => PlayerKind::of(players.side(*side)) == Some(*kind),
+            MenuOption::BotLevel(n) => {
+                search_config.time_limit == Duration::from_secs(BOT_LEVELS[*n].seconds)
+                    && mcts_config.playouts == BOT_LEVELS[*n].playouts
             }
+        };
+
+        if selected {
+            commands.entity(entity).insert(Selected);
+            *color = button_colors.hovered.into();
+        } else {
+            commands.entity(entity).remove::<Selected>();
+            *color = button_colors.normal.into();
         }
     }
 }
@@ -0,0 +1,272 @@
+//! Move notation and written games.
+//!
+//! Squares are named by their column, `a` to `s` from the left, and their row, `1` to `19` from
+//! the bottom, so square (4, 2) is `e3`. Intersections between squares use capital columns:
+//! `E3` is the corner up and to the right of `e3`, intersection (4, 2).
+//!
+//! ```text
+//! Te3          watchtower on square e3
+//! SE3          stone on intersection E3
+//! e3-f4        draught steps from e3 to f4
+//! e3xg5        draught takes over the draught on f4, landing on g5
+//! Ne3          nuke the draught on e3
+//! pass
+//! resign
+//! offer-draw
+//! accept-draw
+//! ```
+//!
+//! A written game starts with headers in brackets, `[Size 13]` and optionally `[Rules ...]`
+//! with a `RuleSet` as RON, followed by a line for each turn with the side and its moves:
+//!
+//! ```text
+//! [Size 9]
+//! White: Tc3
+//! Black: Tg7
+//! White: d4-e5 SA8
+//! ```
+//!
+//! Other headers, like the names of the players, are kept for people and skipped when reading.
+//! Lines starting with `#` are comments.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    board::{Move, MoveError},
+    logic::{BoardConfig, GameLogic, GameResult, Side, MAX_BOARD_SIZE},
+    rules::RuleSet,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotationError {
+    /// Not a square, an intersection or a move token.
+    Move(String),
+    /// Header that can't be read, like a size that isn't a number.
+    Header(String),
+    /// Line that is neither a header nor a turn.
+    Line(String),
+    /// Turn written for the side that isn't to move.
+    WrongSide { line: usize, side: Side },
+    /// Move that can't be played in the game so far.
+    Illegal {
+        line: usize,
+        mv: Move,
+        error: MoveError,
+    },
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Move(word) => write!(f, "{word:?} isn't a move"),
+            NotationError::Header(header) => write!(f, "can't read header {header:?}"),
+            NotationError::Line(line) => write!(f, "{line:?} isn't a header or a turn"),
+            NotationError::WrongSide { line, side } => {
+                write!(f, "line {line}: {side:?} isn't to move")
+            }
+            NotationError::Illegal { line, mv, error } => {
+                write!(f, "line {line}: can't play {mv}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+fn point_name((i, j): (usize, usize), first_column: u8) -> String {
+    format!("{}{}", (first_column + i as u8) as char, j + 1)
+}
+
+fn parse_point(text: &str, columns: std::ops::RangeInclusive<char>) -> Option<(usize, usize)> {
+    let mut chars = text.chars();
+    let column = chars.next().filter(|c| columns.contains(c))?;
+    let row: usize = chars.as_str().parse().ok()?;
+    // no leading zeros or signs, so every point has one name
+    if !(1..=MAX_BOARD_SIZE).contains(&row) || chars.as_str() != row.to_string() {
+        return None;
+    }
+    Some((column as usize - *columns.start() as usize, row - 1))
+}
+
+/// Name of square (i, j), like `e3`.
+pub fn square_name(square: (usize, usize)) -> String {
+    point_name(square, b'a')
+}
+
+/// Name of intersection (i, j), like `E3`.
+pub fn intersection_name(intersection: (usize, usize)) -> String {
+    point_name(intersection, b'A')
+}
+
+pub fn parse_square(text: &str) -> Option<(usize, usize)> {
+    parse_point(text, 'a'..='s')
+}
+
+pub fn parse_intersection(text: &str) -> Option<(usize, usize)> {
+    parse_point(text, 'A'..='R')
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Move::PlaceWatchtower { i, j } => write!(f, "T{}", square_name((i, j))),
+            Move::PlaceStone { i, j } => write!(f, "S{}", intersection_name((i, j))),
+            Move::StepDraught { from, to } => {
+                write!(f, "{}-{}", square_name(from), square_name(to))
+            }
+            Move::TakeOverDraught { from, to } => {
+                write!(f, "{}x{}", square_name(from), square_name(to))
+            }
+            Move::Nuke { i, j } => write!(f, "N{}", square_name((i, j))),
+            Move::Pass => write!(f, "pass"),
+            Move::Resign => write!(f, "resign"),
+            Move::OfferDraw => write!(f, "offer-draw"),
+            Move::AcceptDraw => write!(f, "accept-draw"),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || NotationError::Move(text.into());
+        let mv = match text {
+            "pass" => Move::Pass,
+            "resign" => Move::Resign,
+            "offer-draw" => Move::OfferDraw,
+            "accept-draw" => Move::AcceptDraw,
+            _ => {
+                if let Some((from, to)) = text.split_once('-') {
+                    Move::StepDraught {
+                        from: parse_square(from).ok_or_else(error)?,
+                        to: parse_square(to).ok_or_else(error)?,
+                    }
+                } else if let Some((from, to)) = text.split_once('x') {
+                    Move::TakeOverDraught {
+                        from: parse_square(from).ok_or_else(error)?,
+                        to: parse_square(to).ok_or_else(error)?,
+                    }
+                } else if let Some(square) = text.strip_prefix('T') {
+                    let (i, j) = parse_square(square).ok_or_else(error)?;
+                    Move::PlaceWatchtower { i, j }
+                } else if let Some(intersection) = text.strip_prefix('S') {
+                    let (i, j) = parse_intersection(intersection).ok_or_else(error)?;
+                    Move::PlaceStone { i, j }
+                } else if let Some(square) = text.strip_prefix('N') {
+                    let (i, j) = parse_square(square).ok_or_else(error)?;
+                    Move::Nuke { i, j }
+                } else {
+                    return Err(error());
+                }
+            }
+        };
+        Ok(mv)
+    }
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::White => "White",
+        Side::Black => "Black",
+    }
+}
+
+impl GameLogic {
+    /// The game written down as described in [`crate::notation`].
+    pub fn to_notation(&self) -> String {
+        let board = self.board();
+        let mut text = format!("[Size {}]\n", board.config.size);
+        if board.rules != RuleSet::default() {
+            let rules = ron::ser::to_string(&board.rules).expect("rules are plain data");
+            text += &format!("[Rules {rules}]\n");
+        }
+        if let Some(result) = self.result() {
+            let result = match result {
+                GameResult::Winner(side) => format!("{} wins", side_name(side)),
+                GameResult::Draw => "Draw".into(),
+            };
+            text += &format!("[Result {result}]\n");
+        }
+
+        // a turn goes on as long as the same side moves
+        let mut turn: Option<Side> = None;
+        for entry in self.log() {
+            let side: Side = entry.turn.into();
+            if turn == Some(side) {
+                text += &format!(" {}", entry.mv);
+            } else {
+                if turn.is_some() {
+                    text.push('\n');
+                }
+                text += &format!("{}: {}", side_name(side), entry.mv);
+                turn = Some(side);
+            }
+        }
+        if turn.is_some() {
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Plays a game written as described in [`crate::notation`] from the start.
+    pub fn from_notation(text: &str) -> Result<GameLogic, NotationError> {
+        let mut size = BoardConfig::default().size;
+        let mut rules = RuleSet::default();
+        let mut game: Option<GameLogic> = None;
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+                match key {
+                    "Size" => {
+                        size = value
+                            .trim()
+                            .parse()
+                            .ok()
+                            .filter(|&size| BoardConfig::supports(size))
+                            .ok_or_else(|| NotationError::Header(header.into()))?
+                    }
+                    "Rules" => {
+                        rules = RuleSet::from_ron(value.as_bytes())
+                            .map_err(|_| NotationError::Header(header.into()))?
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            let (side, moves) = line
+                .split_once(':')
+                .ok_or_else(|| NotationError::Line(line.into()))?;
+            let side = match side.trim() {
+                "White" => Side::White,
+                "Black" => Side::Black,
+                _ => return Err(NotationError::Line(line.into())),
+            };
+
+            // headers come before the first move
+            let game = game.get_or_insert_with(|| {
+                GameLogic::with_rules(BoardConfig::new(size), rules.clone())
+            });
+            for word in moves.split_whitespace() {
+                let mv: Move = word.parse()?;
+                if Side::from(game.board().turn) != side {
+                    return Err(NotationError::WrongSide { line: n + 1, side });
+                }
+                game.apply(mv).map_err(|error| NotationError::Illegal {
+                    line: n + 1,
+                    mv,
+                    error,
+                })?;
+            }
+        }
+
+        Ok(game.unwrap_or_else(|| GameLogic::with_rules(BoardConfig::new(size), rules)))
+    }
+}
@@ -0,0 +1,166 @@
+//! Who plays each side.
+//!
+//! A side is played by a person at this machine, a bot or a remote player. People play through
+//! the pointer systems of the board, everybody else hands their moves in through
+//! [`PlayerController::poll`] and the game plays them when it's their turn.
+
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Mutex,
+};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    board::Move,
+    logic::{GameLogic, Side},
+};
+
+/// A computer player.
+pub trait Bot: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Move for the side to move in `game`, or none while the bot is still thinking. Called
+    /// every frame of the bot's turn, so slow bots think in the background.
+    fn poll(&mut self, game: &GameLogic) -> Option<Move>;
+}
+
+/// Plays a random legal move, a baseline for stronger bots.
+pub struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    pub fn new() -> Self {
+        RandomBot {
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Bot that plays the same game every time against the same moves.
+    pub fn seeded(seed: u64) -> Self {
+        RandomBot {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for RandomBot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bot for RandomBot {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn poll(&mut self, game: &GameLogic) -> Option<Move> {
+        game.legal_moves().choose(&mut self.rng).copied()
+    }
+}
+
+/// A player somewhere else, connected by a pair of channels. The other ends are kept by
+/// whatever carries the moves, a network connection or another process.
+pub struct RemotePlayer {
+    moves: Mutex<Receiver<Move>>,
+    played: Sender<Move>,
+}
+
+/// Ends of a [`RemotePlayer`]'s channels for the connection to the remote player.
+pub struct RemoteConnection {
+    /// Moves of the remote player.
+    pub moves: Sender<Move>,
+    /// Every move played in the game, by both sides.
+    pub played: Receiver<Move>,
+}
+
+impl RemotePlayer {
+    pub fn new() -> (RemotePlayer, RemoteConnection) {
+        let (moves_sender, moves) = channel();
+        let (played, played_receiver) = channel();
+        (
+            RemotePlayer {
+                moves: Mutex::new(moves),
+                played,
+            },
+            RemoteConnection {
+                moves: moves_sender,
+                played: played_receiver,
+            },
+        )
+    }
+}
+
+pub enum PlayerController {
+    /// Plays with the pointer on this machine.
+    Human,
+    Bot(Box<dyn Bot>),
+    Remote(RemotePlayer),
+}
+
+impl PlayerController {
+    pub fn is_human(&self) -> bool {
+        matches!(self, PlayerController::Human)
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PlayerController::Human => "Human",
+            PlayerController::Bot(bot) => bot.name(),
+            PlayerController::Remote(_) => "Remote",
+        }
+    }
+
+    /// Move the player wants to play now. People play through the board instead, so there is
+    /// never one for them.
+    pub fn poll(&mut self, game: &GameLogic) -> Option<Move> {
+        match self {
+            PlayerController::Human => None,
+            PlayerController::Bot(bot) => bot.poll(game),
+            PlayerController::Remote(remote) => remote.moves.lock().ok()?.try_recv().ok(),
+        }
+    }
+
+    /// Tells the player about a move played in the game.
+    pub fn observe(&self, mv: Move) {
+        if let PlayerController::Remote(remote) = self {
+            // a closed connection just stops hearing about the game
+            let _ = remote.played.send(mv);
+        }
+    }
+}
+
+/// Controllers of both sides for the game being played.
+#[derive(Resource)]
+pub struct Players {
+    pub white: PlayerController,
+    pub black: PlayerController,
+}
+
+impl Default for Players {
+    fn default() -> Self {
+        Players {
+            white: PlayerController::Human,
+            black: PlayerController::Human,
+        }
+    }
+}
+
+impl Players {
+    pub fn side(&self, side: Side) -> &PlayerController {
+        match side {
+            Side::White => &self.white,
+            Side::Black => &self.black,
+        }
+    }
+
+    pub fn side_mut(&mut self, side: Side) -> &mut PlayerController {
+        match side {
+            Side::White => &mut self.white,
+            Side::Black => &mut self.black,
+        }
+    }
+}
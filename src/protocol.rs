@@ -0,0 +1,449 @@
+//! Line protocol for engines running as another process.
+//!
+//! Like UCI or GTP, the game and the engine talk in lines over the engine's stdin and stdout.
+//! The game sends commands:
+//!
+//! ```text
+//! wtd                              handshake, answered by `id name NAME` and `wtdok`
+//! isready                          answered by `readyok` once earlier commands are done
+//! rules RULES                      rules of the next positions, a `RuleSet` on one line of RON
+//! position SIZE [moves MOVE...]    new game on a SIZE board with the moves played so far
+//! move MOVE                        move played in the current game
+//! time MILLISECONDS                time left on the clock of the side to move
+//! go                               asks for a move of the side to move
+//! quit
+//! ```
+//!
+//! and the engine answers:
+//!
+//! ```text
+//! id name NAME
+//! wtdok
+//! readyok
+//! bestmove MOVE                    answer to `go`
+//! info TEXT                        anything worth logging
+//! ```
+//!
+//! Moves are written in the notation of [`crate::notation`], like `e5-f6` or `pass`. Engines
+//! answer lines they don't understand with `info` and go on. A `position` with a move that
+//! can't be played is answered the same way and leaves the previous position.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, Command as Process, Stdio},
+    str::FromStr,
+    sync::{
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+
+use crate::{board::Move, logic::GameLogic, players::Bot, rules::RuleSet};
+
+/// How long an engine has to answer the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Command line of the engine that can be picked as a player in the menu, if there is one.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct EngineCommand(pub Option<String>);
+
+/// Line sent by the game to the engine.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Hello,
+    IsReady,
+    Rules(RuleSet),
+    Position {
+        size: usize,
+        moves: Vec<Move>,
+    },
+    Move(Move),
+    /// Time left on the clock of the side to move.
+    Time(Duration),
+    Go,
+    Quit,
+}
+
+/// Line sent by the engine to the game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reply {
+    /// Name of the engine.
+    Id(String),
+    HelloOk,
+    ReadyOk,
+    BestMove(Move),
+    Info(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtocolError {
+    Empty,
+    /// First word isn't a command or reply.
+    Unknown(String),
+    /// Command is missing its argument or has one too many.
+    Arguments(String),
+    Number(String),
+    Move(String),
+    Rules(String),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Empty => write!(f, "empty line"),
+            ProtocolError::Unknown(word) => write!(f, "unknown command {word:?}"),
+            ProtocolError::Arguments(line) => write!(f, "wrong arguments in {line:?}"),
+            ProtocolError::Number(word) => write!(f, "{word:?} isn't a number"),
+            ProtocolError::Move(word) => write!(f, "{word:?} isn't a move"),
+            ProtocolError::Rules(err) => write!(f, "can't read rules: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+fn parse_move(word: &str) -> Result<Move, ProtocolError> {
+    word.parse().map_err(|_| ProtocolError::Move(word.into()))
+}
+
+fn parse_number<T: FromStr>(word: &str) -> Result<T, ProtocolError> {
+    word.parse().map_err(|_| ProtocolError::Number(word.into()))
+}
+
+/// First word of `line` and the rest of it.
+fn split_line(line: &str) -> Result<(&str, &str), ProtocolError> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Err(ProtocolError::Empty);
+    }
+    Ok(match line.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (line, ""),
+    })
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Hello => write!(f, "wtd"),
+            Command::IsReady => write!(f, "isready"),
+            Command::Rules(rules) => write!(
+                f,
+                "rules {}",
+                ron::ser::to_string(rules).expect("rules are plain data")
+            ),
+            Command::Position { size, moves } => {
+                write!(f, "position {size}")?;
+                if !moves.is_empty() {
+                    write!(f, " moves")?;
+                    for mv in moves.iter() {
+                        write!(f, " {mv}")?;
+                    }
+                }
+                Ok(())
+            }
+            Command::Move(mv) => write!(f, "move {mv}"),
+            Command::Time(time) => write!(f, "time {}", time.as_millis()),
+            Command::Go => write!(f, "go"),
+            Command::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = ProtocolError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (word, rest) = split_line(line)?;
+        let no_arguments = |command| {
+            if rest.is_empty() {
+                Ok(command)
+            } else {
+                Err(ProtocolError::Arguments(line.into()))
+            }
+        };
+
+        match word {
+            "wtd" => no_arguments(Command::Hello),
+            "isready" => no_arguments(Command::IsReady),
+            "go" => no_arguments(Command::Go),
+            "quit" => no_arguments(Command::Quit),
+            "rules" => RuleSet::from_ron(rest.as_bytes())
+                .map(Command::Rules)
+                .map_err(|err| ProtocolError::Rules(err.to_string())),
+            "position" => {
+                let mut words = rest.split_whitespace();
+                let size = parse_number(
+                    words
+                        .next()
+                        .ok_or_else(|| ProtocolError::Arguments(line.into()))?,
+                )?;
+                let moves = match words.next() {
+                    None => vec![],
+                    Some("moves") => words.map(parse_move).collect::<Result<_, _>>()?,
+                    Some(_) => return Err(ProtocolError::Arguments(line.into())),
+                };
+                Ok(Command::Position { size, moves })
+            }
+            "move" => parse_move(rest).map(Command::Move),
+            "time" => Ok(Command::Time(Duration::from_millis(parse_number(rest)?))),
+            _ => Err(ProtocolError::Unknown(word.into())),
+        }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Id(name) => write!(f, "id name {name}"),
+            Reply::HelloOk => write!(f, "wtdok"),
+            Reply::ReadyOk => write!(f, "readyok"),
+            Reply::BestMove(mv) => write!(f, "bestmove {mv}"),
+            Reply::Info(text) => write!(f, "info {text}"),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = ProtocolError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (word, rest) = split_line(line)?;
+        match word {
+            "id" => match split_line(rest) {
+                Ok(("name", name)) => Ok(Reply::Id(name.into())),
+                _ => Err(ProtocolError::Arguments(line.into())),
+            },
+            "wtdok" => Ok(Reply::HelloOk),
+            "readyok" => Ok(Reply::ReadyOk),
+            "bestmove" => parse_move(rest).map(Reply::BestMove),
+            "info" => Ok(Reply::Info(rest.into())),
+            _ => Err(ProtocolError::Unknown(word.into())),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+/// Bot that runs an engine as a subprocess and talks to it over the protocol. The engine is
+/// sent the whole game before every move, so it doesn't have to keep track of it. Lines are
+/// read and written on threads of their own, so an engine that stops reading or answering
+/// doesn't hold up the game.
+pub struct SubprocessBot {
+    child: Child,
+    commands: Sender<Command>,
+    replies: Mutex<Receiver<Reply>>,
+    id: String,
+    /// Whether the engine answered the handshake.
+    ready: bool,
+    started_at: Instant,
+    /// Position the engine was last asked to move in.
+    asked: Option<u64>,
+    asked_at: Instant,
+    /// `go` commands not answered yet, answers to older ones are dropped.
+    pending: usize,
+    /// Time left on the engine's clock for the rest of the game.
+    clock: Duration,
+}
+
+impl SubprocessBot {
+    /// Starts `command`, a program followed by its arguments separated by spaces, and greets it.
+    /// The handshake is finished while polling, so the game doesn't wait for a slow engine, or
+    /// with `wait_ready`. The engine gets `clock` for all its moves.
+    pub fn spawn(command: &str, clock: Duration) -> io::Result<Self> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty engine command"))?;
+        let mut child = Process::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        let (commands, receiver) = channel::<Command>();
+        thread::spawn(move || {
+            for command in receiver {
+                if writeln!(stdin, "{command}")
+                    .and_then(|_| stdin.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let (sender, replies) = channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                match line.parse::<Reply>() {
+                    Ok(reply) => {
+                        if sender.send(reply).is_err() {
+                            break;
+                        }
+                    }
+                    Err(ProtocolError::Empty) => {}
+                    Err(err) => warn!("engine said {line:?}: {err}"),
+                }
+            }
+        });
+
+        let bot = SubprocessBot {
+            child,
+            commands,
+            replies: Mutex::new(replies),
+            id: command.into(),
+            ready: false,
+            started_at: Instant::now(),
+            asked: None,
+            asked_at: Instant::now(),
+            pending: 0,
+            clock,
+        };
+        bot.send(Command::Hello)?;
+        Ok(bot)
+    }
+
+    /// Waits for the handshake, for callers that can block.
+    pub fn wait_ready(&mut self) -> io::Result<()> {
+        while !self.ready {
+            let timeout = HANDSHAKE_TIMEOUT.saturating_sub(self.started_at.elapsed());
+            match self.replies.get_mut().unwrap().recv_timeout(timeout) {
+                Ok(reply) => self.handshake(reply),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "engine didn't answer the handshake",
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "engine quit during the handshake",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handshake(&mut self, reply: Reply) {
+        match reply {
+            Reply::Id(id) => self.id = id,
+            Reply::HelloOk => {
+                self.ready = true;
+                info!("engine {} is ready", self.id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Name the engine gave itself.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Hands `command` to the writer thread, which is gone once the engine stopped reading.
+    fn send(&self, command: Command) -> io::Result<()> {
+        self.commands
+            .send(command)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "engine stopped reading"))
+    }
+
+    /// Sends the game and asks for a move.
+    fn ask(&mut self, game: &GameLogic) -> io::Result<()> {
+        let board = game.board();
+        self.send(Command::Rules(board.rules.clone()))?;
+        self.send(Command::Position {
+            size: board.config.size,
+            moves: game.log().iter().map(|entry| entry.mv).collect(),
+        })?;
+        self.send(Command::Time(self.clock))?;
+        self.send(Command::Go)?;
+        self.pending += 1;
+        Ok(())
+    }
+}
+
+impl Bot for SubprocessBot {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn poll(&mut self, game: &GameLogic) -> Option<Move> {
+        while !self.ready {
+            match self.replies.get_mut().unwrap().try_recv() {
+                Ok(reply) => self.handshake(reply),
+                Err(TryRecvError::Empty) if self.started_at.elapsed() < HANDSHAKE_TIMEOUT => {
+                    return None
+                }
+                Err(TryRecvError::Empty) => {
+                    warn!("engine {} didn't answer the handshake, resigning", self.id);
+                    return Some(Move::Resign);
+                }
+                Err(TryRecvError::Disconnected) => {
+                    warn!("engine {} quit during the handshake, resigning", self.id);
+                    return Some(Move::Resign);
+                }
+            }
+        }
+
+        let hash = game.hash();
+        if self.asked != Some(hash) {
+            self.asked = Some(hash);
+            self.asked_at = Instant::now();
+            if let Err(err) = self.ask(game) {
+                warn!("engine {} can't be reached, resigning: {err}", self.id);
+                return Some(Move::Resign);
+            }
+        }
+
+        loop {
+            match self.replies.get_mut().unwrap().try_recv() {
+                Ok(Reply::BestMove(mv)) => {
+                    self.pending = self.pending.saturating_sub(1);
+                    if self.pending == 0 {
+                        self.clock = self.clock.saturating_sub(self.asked_at.elapsed());
+                        self.asked = None;
+                        // asking again would get the same move, so the game can't go on
+                        if game.clone().apply(mv).is_err() {
+                            warn!("engine {} played illegal {mv}, resigning", self.id);
+                            return Some(Move::Resign);
+                        }
+                        return Some(mv);
+                    }
+                }
+                Ok(Reply::Info(text)) => info!("{}: {text}", self.id),
+                Ok(_) => {}
+                Err(TryRecvError::Empty) if self.asked_at.elapsed() < self.clock => return None,
+                Err(TryRecvError::Empty) => {
+                    warn!("engine {} ran out of time, resigning", self.id);
+                    return Some(Move::Resign);
+                }
+                Err(TryRecvError::Disconnected) => {
+                    warn!("engine {} quit, resigning", self.id);
+                    return Some(Move::Resign);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SubprocessBot {
+    fn drop(&mut self) {
+        let _ = self.send(Command::Quit);
+        // engines that ignore quit don't outlive the game
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,87 @@
+//! Records of played games.
+//!
+//! A record keeps the rules, the board size and every move, so the game can be replayed move by
+//! move. Records are stored as RON, like the rule presets.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::{Move, MoveError},
+    logic::{BoardConfig, Ending, GameLogic, GameResult},
+    rules::{RuleSet, RuleSetLoaderError},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Names of the players.
+    pub white: String,
+    pub black: String,
+    pub size: usize,
+    pub rules: RuleSet,
+    /// Every move of the game, resigning and draw offers included.
+    pub moves: Vec<Move>,
+    /// Result and ending of the game, none if it isn't over.
+    pub result: Option<GameResult>,
+    pub ending: Option<Ending>,
+}
+
+#[derive(Debug)]
+pub enum RecordError {
+    /// Board size that can't be played.
+    Size(usize),
+    /// Rules the game can't be played with, like a preset that doesn't load.
+    Rules(RuleSetLoaderError),
+    Move(MoveError),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Size(size) => write!(f, "board size {size} isn't supported"),
+            RecordError::Rules(err) => write!(f, "rules can't be played: {err}"),
+            RecordError::Move(err) => write!(f, "move can't be played: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl GameRecord {
+    pub fn new(game: &GameLogic, white: &str, black: &str) -> Self {
+        let board = game.board();
+        GameRecord {
+            white: white.into(),
+            black: black.into(),
+            size: board.config.size,
+            rules: board.rules.clone(),
+            moves: game.log().iter().map(|entry| entry.mv).collect(),
+            result: game.result(),
+            ending: game.ending(),
+        }
+    }
+
+    /// Plays the moves of the record again from the start. Records are read from files, so the
+    /// size and rules are checked first.
+    pub fn replay(&self) -> Result<GameLogic, RecordError> {
+        if !BoardConfig::supports(self.size) {
+            return Err(RecordError::Size(self.size));
+        }
+        self.rules.check().map_err(RecordError::Rules)?;
+        let mut game = GameLogic::with_rules(BoardConfig::new(self.size), self.rules.clone());
+        for mv in self.moves.iter() {
+            game.apply(*mv).map_err(RecordError::Move)?;
+        }
+        Ok(game)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("records are plain data")
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_str(text)
+    }
+}
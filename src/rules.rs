@@ -0,0 +1,241 @@
+use std::{fmt, fs, path::Path};
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::{logic::MAX_BOARD_SIZE, stats::ScoreWeights, turns::TurnStructure};
+
+/// Tunable parameters of the game. Named presets are loaded from `assets/rules/*.rules.ron`,
+/// missing fields fall back to the classic rules.
+#[derive(Resource, Asset, TypePath, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    pub name: String,
+    /// Squares around a nuked draught that get cleared, 1 is a 3x3 blast. Stones on the corners
+    /// of those squares are cleared too.
+    pub nuke_radius: usize,
+    /// Stones a chain needs before it can enclose territory.
+    pub min_region_stones: usize,
+    /// Draughts around a new watchtower, in the order they are numbered.
+    pub watchtower_draughts: Vec<(i32, i32)>,
+    /// Stones around a new watchtower, as offsets of intersections from the tower square.
+    pub watchtower_stones: Vec<(i32, i32)>,
+    /// Watchtowers can't be placed closer than this to each other.
+    pub min_watchtower_distance: usize,
+    /// Moves after both watchtowers are placed before the game is stopped and adjudicated.
+    pub move_limit: Option<usize>,
+    /// How the position is valued for adjudication, the HUD and analysis.
+    pub score_weights: ScoreWeights,
+    /// What happens when a position comes up again.
+    pub repetition: Repetition,
+    /// Whether a draught that can take over again after a takeover has to.
+    pub chained_takeovers: ChainedTakeovers,
+    /// Steps of a turn once both watchtowers are placed.
+    pub turn_structure: TurnStructure,
+    /// Whether the game stops and is adjudicated once both players pass every step of their
+    /// turns, one after the other, like two passes in Go.
+    pub end_on_passes: bool,
+}
+
+/// A draught that took over an enemy draught and can jump over another one keeps going within
+/// the same turn, like multi-captures in draughts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChainedTakeovers {
+    /// The chain goes on as long as there is a draught to take over.
+    Compulsory,
+    /// The player can stop the chain after any takeover.
+    Optional,
+}
+
+/// Positions are the same when the pieces, the side to move and the phase are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Repetition {
+    /// Moves that bring back an earlier position are illegal (superko).
+    Forbidden,
+    /// The game is drawn once a position has been on the board this many times.
+    DrawAfter(usize),
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            name: "Classic".into(),
+            nuke_radius: 1,
+            min_region_stones: 3,
+            watchtower_draughts: vec![
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+            watchtower_stones: vec![
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (1, -2),
+                (0, 1),
+                (0, -2),
+                (-1, 1),
+                (-1, -2),
+                (-2, 1),
+                (-2, 0),
+                (-2, -1),
+                (-2, -2),
+            ],
+            min_watchtower_distance: 5,
+            move_limit: None,
+            score_weights: ScoreWeights::default(),
+            repetition: Repetition::DrawAfter(3),
+            chained_takeovers: ChainedTakeovers::Optional,
+            turn_structure: TurnStructure::default(),
+            end_on_passes: true,
+        }
+    }
+}
+
+// ------
+// Loader
+// ------
+
+#[derive(Default)]
+pub struct RuleSetLoader;
+
+#[derive(Debug)]
+pub enum RuleSetLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    NukeRadius(usize),
+    /// Regions of no stones or more stones than any board holds.
+    RegionStones(usize),
+    /// Watchtower draughts on the tower square, on the same square or further than any board.
+    WatchtowerDraughts,
+    /// Watchtower stones further than any board.
+    WatchtowerStones,
+    WatchtowerDistance(usize),
+    /// Draws after fewer than two occurrences, which every position has had.
+    Repetition(usize),
+    TurnStructure,
+}
+
+impl fmt::Display for RuleSetLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSetLoaderError::Io(error) => write!(f, "could not read rule set: {error}"),
+            RuleSetLoaderError::Ron(error) => write!(f, "could not parse rule set: {error}"),
+            RuleSetLoaderError::NukeRadius(radius) => {
+                write!(f, "nuke radius {radius} is wider than any board")
+            }
+            RuleSetLoaderError::RegionStones(n) => write!(
+                f,
+                "region stones {n} isn't between 1 and {}",
+                MAX_BOARD_SIZE.pow(2)
+            ),
+            RuleSetLoaderError::WatchtowerDraughts => write!(
+                f,
+                "watchtower draughts need different squares around the tower"
+            ),
+            RuleSetLoaderError::WatchtowerStones => {
+                write!(f, "watchtower stones don't fit on any board")
+            }
+            RuleSetLoaderError::WatchtowerDistance(distance) => write!(
+                f,
+                "watchtower distance {distance} isn't between 1 and {MAX_BOARD_SIZE}"
+            ),
+            RuleSetLoaderError::Repetition(n) => {
+                write!(f, "positions are on the board at least once, not {n} times")
+            }
+            RuleSetLoaderError::TurnStructure => {
+                write!(
+                    f,
+                    "turn structure needs steps with at least one action each"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleSetLoaderError {}
+
+impl RuleSet {
+    /// Reads a preset written as RON and checks that the game can be played with it.
+    pub fn from_ron(bytes: &[u8]) -> Result<RuleSet, RuleSetLoaderError> {
+        let rules: RuleSet = ron::de::from_bytes(bytes).map_err(RuleSetLoaderError::Ron)?;
+        rules.check()?;
+        Ok(rules)
+    }
+
+    /// Rejects rules the game can't be played with, which serde doesn't know about.
+    pub(crate) fn check(&self) -> Result<(), RuleSetLoaderError> {
+        if self.nuke_radius >= MAX_BOARD_SIZE {
+            return Err(RuleSetLoaderError::NukeRadius(self.nuke_radius));
+        }
+        if !(1..=MAX_BOARD_SIZE.pow(2)).contains(&self.min_region_stones) {
+            return Err(RuleSetLoaderError::RegionStones(self.min_region_stones));
+        }
+
+        let on_a_board = |(di, dj): &(i32, i32)| {
+            di.unsigned_abs() < MAX_BOARD_SIZE as u32 && dj.unsigned_abs() < MAX_BOARD_SIZE as u32
+        };
+        let draughts = &self.watchtower_draughts;
+        if !draughts.iter().all(|d| *d != (0, 0) && on_a_board(d))
+            || (1..draughts.len()).any(|n| draughts[..n].contains(&draughts[n]))
+        {
+            return Err(RuleSetLoaderError::WatchtowerDraughts);
+        }
+        if !self.watchtower_stones.iter().all(on_a_board) {
+            return Err(RuleSetLoaderError::WatchtowerStones);
+        }
+        if !(1..=MAX_BOARD_SIZE).contains(&self.min_watchtower_distance) {
+            return Err(RuleSetLoaderError::WatchtowerDistance(
+                self.min_watchtower_distance,
+            ));
+        }
+
+        if let Repetition::DrawAfter(n @ (0 | 1)) = self.repetition {
+            return Err(RuleSetLoaderError::Repetition(n));
+        }
+
+        if !self.turn_structure.is_valid() {
+            return Err(RuleSetLoaderError::TurnStructure);
+        }
+        Ok(())
+    }
+
+    /// Reads a preset file, for tools that run without the asset server.
+    pub fn read(path: &Path) -> Result<RuleSet, RuleSetLoaderError> {
+        let bytes = fs::read(path).map_err(RuleSetLoaderError::Io)?;
+        RuleSet::from_ron(&bytes)
+    }
+}
+
+impl AssetLoader for RuleSetLoader {
+    type Asset = RuleSet;
+    type Settings = ();
+    type Error = RuleSetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<RuleSet, RuleSetLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(RuleSetLoaderError::Io)?;
+            RuleSet::from_ron(&bytes)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rules.ron"]
+    }
+}
@@ -0,0 +1,103 @@
+//! Saved games, so a game can be closed and continued later.
+//!
+//! A save is the record of the game, with the players, rules and every move, together with a
+//! diagram of the position and whose turn it is. Loading plays the moves again and checks that
+//! they lead to the saved position, so a save from different rules isn't silently changed.
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    logic::GameLogic,
+    record::{GameRecord, RecordError},
+};
+
+/// File in the home directory the game is saved to after every move.
+const SAVE_FILE: &str = ".watchtower_defence.save.ron";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub record: GameRecord,
+    /// Diagram of the position as drawn by [`crate::ascii`], ending with the side to move.
+    pub position: String,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Ron(ron::error::SpannedError),
+    /// Size, rules or a move of the saved game can't be played.
+    Record(RecordError),
+    /// The moves don't lead to the saved position.
+    Position,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "can't access the save: {err}"),
+            SaveError::Ron(err) => write!(f, "can't read the save: {err}"),
+            SaveError::Record(err) => write!(f, "can't play the saved game: {err}"),
+            SaveError::Position => write!(f, "saved moves don't lead to the saved position"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Game picked with "Continue" in the menu, taken by the board when it's spawned.
+#[derive(Resource, Default)]
+pub struct ResumedGame(pub Option<GameLogic>);
+
+/// Where the game is saved, none where there is no file system.
+pub fn save_path() -> Option<PathBuf> {
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    Some(home.map(PathBuf::from).unwrap_or_default().join(SAVE_FILE))
+}
+
+impl SavedGame {
+    pub fn new(game: &GameLogic, white: &str, black: &str) -> Self {
+        SavedGame {
+            record: GameRecord::new(game, white, black),
+            position: game.board().to_string(),
+        }
+    }
+
+    /// Plays the saved moves again, up to the saved position.
+    pub fn resume(&self) -> Result<GameLogic, SaveError> {
+        let game = self.record.replay().map_err(SaveError::Record)?;
+        if game.board().to_string() != self.position {
+            return Err(SaveError::Position);
+        }
+        Ok(game)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("saves are plain data")
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_str(text)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        let text = fs::read_to_string(path).map_err(SaveError::Io)?;
+        SavedGame::from_ron(&text).map_err(SaveError::Ron)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        // closing the window halfway through writing leaves the previous save
+        let partial = path.with_extension("partial");
+        fs::write(&partial, self.to_ron()).map_err(SaveError::Io)?;
+        fs::rename(&partial, path).map_err(SaveError::Io)
+    }
+}
@@ -0,0 +1,361 @@
+//! Alpha-beta search for the computer opponent.
+//!
+//! Negamax over the steps of turns with iterative deepening and a transposition table keyed by
+//! the zobrist hash. A turn can have several steps played by the same side, so the score only
+//! flips sign when the side to move changes. Repetitions, passes and move limits are only
+//! checked at the root, deeper in the tree the board alone decides.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy::tasks::{block_on, AsyncComputeTaskPool, Task, TaskPool};
+use bevy::utils::{Duration, Instant};
+
+use crate::{
+    bitboard::Bitboard,
+    board::{BoardState, Move},
+    logic::{GameLogic, GamePhase, Side},
+    players::Bot,
+    zobrist,
+};
+
+/// Score of a won position, wins found sooner score higher.
+pub const WIN: i32 = 1_000_000;
+
+/// Enemy draught close enough to nuke a watchtower.
+const NUKE_THREAT: i32 = 20;
+/// Enemy draught one step away from nuking a watchtower.
+const NUKE_APPROACH: i32 = 5;
+
+/// Positions kept in the transposition table before it's cleared.
+const TABLE_SIZE: usize = 1 << 20;
+
+/// How long and how deep the search goes. The search stops at whichever limit comes first and
+/// plays the best move of the last depth it finished.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchConfig {
+    /// Steps of turns to look ahead.
+    pub max_depth: usize,
+    pub time_limit: Duration,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            max_depth: 8,
+            time_limit: Duration::from_secs(3),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    /// Value of the position for the side to move.
+    pub score: i32,
+    /// Deepest search that finished.
+    pub depth: usize,
+    pub nodes: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    depth: usize,
+    score: i32,
+    bound: Bound,
+    best_move: Option<Move>,
+}
+
+struct Search {
+    deadline: Instant,
+    nodes: u64,
+    stopped: bool,
+    table: HashMap<u64, Entry>,
+}
+
+/// Value of `board` for `side`: the score of the rules, territory and draughts included, and
+/// how exposed both watchtowers are to nukes.
+pub fn evaluate(board: &BoardState, side: Side) -> i32 {
+    if board.phase == GamePhase::GameOver {
+        let winner = board.winner().or(board.score().leader());
+        return match winner {
+            Some(winner) if winner == side => WIN,
+            Some(_) => -WIN,
+            None => 0,
+        };
+    }
+
+    board.score().margin(side) + threats(board, side.opposite()) - threats(board, side)
+}
+
+/// How much the enemy draughts around the watchtower of `side` threaten it.
+fn threats(board: &BoardState, side: Side) -> i32 {
+    let Some(watchtower) = board.army(side).watchtower else {
+        return 0;
+    };
+
+    // a nuke reaches as far from the draught as the draught is from the watchtower
+    let mut reach = Bitboard::from_point(watchtower);
+    for _ in 0..board.rules.nuke_radius {
+        reach = reach.king_neighbours();
+    }
+    let approach = reach.king_neighbours() - reach;
+
+    let enemy = board.army(side.opposite()).draughts;
+    (enemy & reach).count() as i32 * NUKE_THREAT + (enemy & approach).count() as i32 * NUKE_APPROACH
+}
+
+/// Moves worth searching: stones are only tried near other stones and watchtowers, everything
+/// else is kept. Captures and nukes come first so they cut the search early.
+pub(crate) fn candidate_moves(board: &BoardState) -> Vec<Move> {
+    let mut near = board.white.stones | board.black.stones;
+    for watchtower in [board.white.watchtower, board.black.watchtower]
+        .into_iter()
+        .flatten()
+    {
+        near.insert(watchtower);
+    }
+    let near = near.king_neighbours().king_neighbours();
+
+    let mut moves: Vec<Move> = board
+        .legal_moves()
+        .into_iter()
+        .filter(|mv| match mv {
+            Move::PlaceStone { i, j } => near.contains((*i, *j)),
+            _ => true,
+        })
+        .collect();
+    moves.sort_by_key(|mv| match mv {
+        Move::TakeOverDraught { .. } => 0,
+        Move::Nuke { .. } => 1,
+        Move::Pass => 3,
+        _ => 2,
+    });
+    moves
+}
+
+impl Search {
+    #[allow(clippy::manual_is_multiple_of)]
+    fn negamax(
+        &mut self,
+        board: &BoardState,
+        depth: usize,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+        if self.nodes % 1024 == 0 && Instant::now() >= self.deadline {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        let side: Side = board.turn.into();
+        if depth == 0 || board.phase == GamePhase::GameOver {
+            let score = evaluate(board, side);
+            // prefer quick wins and slow losses
+            return match score {
+                WIN => WIN - ply,
+                score if score == -WIN => -WIN + ply,
+                score => score,
+            };
+        }
+
+        let hash = zobrist::hash(board);
+        let entry = self.table.get(&hash).copied();
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth) {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                _ => {}
+            }
+        }
+
+        let mut moves = candidate_moves(board);
+        if let Some(best) = entry.and_then(|entry| entry.best_move) {
+            if let Some(n) = moves.iter().position(|mv| *mv == best) {
+                moves[..=n].rotate_right(1);
+            }
+        }
+
+        let alpha_start = alpha;
+        let mut best = (-WIN - 1, None);
+        for mv in moves {
+            let mut child = board.clone();
+            if child.apply(mv).is_err() {
+                continue;
+            }
+
+            let score = if child.turn == board.turn {
+                self.negamax(&child, depth - 1, ply + 1, alpha, beta)
+            } else {
+                -self.negamax(&child, depth - 1, ply + 1, -beta, -alpha)
+            };
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best.0 {
+                best = (score, Some(mv));
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        // only rejected moves, can't happen with legal ones
+        if best.1.is_none() {
+            return evaluate(board, side);
+        }
+
+        let bound = if best.0 <= alpha_start {
+            Bound::Upper
+        } else if best.0 >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        if self.table.len() >= TABLE_SIZE {
+            self.table.clear();
+        }
+        self.table.insert(
+            hash,
+            Entry {
+                depth,
+                score: best.0,
+                bound,
+                best_move: best.1,
+            },
+        );
+
+        best.0
+    }
+
+    /// Searches the legal moves of `game` to `depth`, the best one found before first.
+    fn root(&mut self, game: &GameLogic, moves: &mut [Move], depth: usize) -> Option<(Move, i32)> {
+        let board = game.board();
+        let mut alpha = -WIN - 1;
+        let mut best = None;
+        for n in 0..moves.len() {
+            let mv = moves[n];
+            let mut child = board.clone();
+            if child.apply(mv).is_err() {
+                continue;
+            }
+
+            let score = if child.turn == board.turn {
+                self.negamax(&child, depth - 1, 1, alpha, WIN + 1)
+            } else {
+                -self.negamax(&child, depth - 1, 1, -WIN - 1, -alpha)
+            };
+            if self.stopped {
+                return None;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best = Some((mv, score));
+                moves[..=n].rotate_right(1);
+            }
+        }
+        best
+    }
+}
+
+/// Best move for the side to move in `game` within the limits of `config`.
+pub fn search(game: &GameLogic, config: &SearchConfig) -> SearchResult {
+    let mut search = Search {
+        deadline: Instant::now() + config.time_limit,
+        nodes: 0,
+        stopped: false,
+        table: HashMap::new(),
+    };
+
+    let mut moves = game.legal_moves();
+    let mut result = SearchResult {
+        best_move: moves.first().copied(),
+        score: evaluate(game.board(), game.board().turn.into()),
+        depth: 0,
+        nodes: 0,
+    };
+    if moves.len() < 2 {
+        return result;
+    }
+
+    for depth in 1..=config.max_depth {
+        let Some((best_move, score)) = search.root(game, &mut moves, depth) else {
+            break;
+        };
+        result.best_move = Some(best_move);
+        result.score = score;
+        result.depth = depth;
+
+        // nothing changes a forced win or loss
+        if score.abs() >= WIN - depth as i32 {
+            break;
+        }
+    }
+
+    result.nodes = search.nodes;
+    result
+}
+
+/// Bot playing the best move alpha-beta search finds, searching on the async compute pool so
+/// frames keep coming.
+pub struct AlphaBetaBot {
+    pub config: SearchConfig,
+    /// Position being searched and the search.
+    task: Option<(u64, Task<SearchResult>)>,
+}
+
+impl AlphaBetaBot {
+    pub fn new(config: SearchConfig) -> Self {
+        AlphaBetaBot { config, task: None }
+    }
+}
+
+impl Bot for AlphaBetaBot {
+    fn name(&self) -> &str {
+        "Alpha-Beta"
+    }
+
+    fn poll(&mut self, game: &GameLogic) -> Option<Move> {
+        let hash = game.hash();
+        match &self.task {
+            Some((searched, task)) if *searched == hash => {
+                if !task.is_finished() {
+                    return None;
+                }
+            }
+            // dropping a search of another position cancels it
+            _ => {
+                let game = game.clone();
+                let config = self.config;
+                // the pool is only set up by the app, outside of it the bot starts one
+                let task = AsyncComputeTaskPool::get_or_init(TaskPool::default)
+                    .spawn(async move { search(&game, &config) });
+                self.task = Some((hash, task));
+                return None;
+            }
+        }
+
+        let (_, task) = self.task.take()?;
+        let result = block_on(task);
+        info!(
+            "searched {} nodes to depth {}, score {}",
+            result.nodes, result.depth, result.score
+        );
+        result.best_move
+    }
+}
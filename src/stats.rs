@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::logic::Side;
+
+/// What one side has on the board.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct SideStats {
+    /// Squares enclosed by the side's stones.
+    pub territory: usize,
+    /// Draughts the side owns, captured ones included.
+    pub draughts: usize,
+    /// Enemy draughts the side took over during the game.
+    pub captured_draughts: usize,
+    pub watchtower: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct GameStats {
+    /// Moves played after both watchtowers were placed.
+    pub n_moves: usize,
+    pub white: SideStats,
+    pub black: SideStats,
+}
+
+/// How much each part of the position is worth. Part of the rule set, so presets can change
+/// what being ahead means.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreWeights {
+    pub territory: i32,
+    pub draughts: i32,
+    pub captured_draughts: i32,
+    pub watchtower: i32,
+}
+
+/// Weighted value of both sides' positions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash)]
+pub struct Score {
+    pub white: i32,
+    pub black: i32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            territory: 1,
+            draughts: 3,
+            captured_draughts: 2,
+            watchtower: 50,
+        }
+    }
+}
+
+impl SideStats {
+    pub fn score(&self, weights: &ScoreWeights) -> i32 {
+        self.territory as i32 * weights.territory
+            + self.draughts as i32 * weights.draughts
+            + self.captured_draughts as i32 * weights.captured_draughts
+            + i32::from(self.watchtower) * weights.watchtower
+    }
+}
+
+impl GameStats {
+    pub fn side(&self, side: Side) -> &SideStats {
+        match side {
+            Side::Black => &self.black,
+            Side::White => &self.white,
+        }
+    }
+
+    pub fn score(&self, weights: &ScoreWeights) -> Score {
+        Score {
+            white: self.white.score(weights),
+            black: self.black.score(weights),
+        }
+    }
+}
+
+impl Score {
+    pub fn side(&self, side: Side) -> i32 {
+        match side {
+            Side::Black => self.black,
+            Side::White => self.white,
+        }
+    }
+
+    /// How far `side` is ahead, negative when it's behind.
+    pub fn margin(&self, side: Side) -> i32 {
+        self.side(side) - self.side(side.opposite())
+    }
+
+    /// Side that is ahead, none when the scores are equal.
+    pub fn leader(&self) -> Option<Side> {
+        match self.white.cmp(&self.black) {
+            std::cmp::Ordering::Greater => Some(Side::White),
+            std::cmp::Ordering::Less => Some(Side::Black),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
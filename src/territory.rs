@@ -0,0 +1,91 @@
+//! Territory enclosed by chains of stones.
+//!
+//! Stones sit on intersections, the corners of squares. Two neighbouring stones of a chain make a
+//! wall: along the edge between them, or across the square between them when they are diagonal
+//! neighbours. Every square is split by its diagonals into four triangles and the triangles that
+//! can't be reached from outside the board without crossing a wall are enclosed. A square is
+//! territory when most of it, three triangles or more, is enclosed. An intersection is enclosed
+//! when the triangles around it are.
+
+use crate::{bitboard::Bitboard, logic::BoardConfig, rules::RuleSet};
+
+/// Area enclosed by one side's stones.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Enclosure {
+    /// Squares enclosed by the stones.
+    pub territory: Bitboard,
+    /// Intersections inside of the enclosed area. Enemy stones on them are captured.
+    pub intersections: Bitboard,
+}
+
+/// Stones of chains long enough to enclose territory.
+pub fn wall_stones(rules: &RuleSet, stones: Bitboard) -> Bitboard {
+    let mut walls = Bitboard::EMPTY;
+    let mut unvisited = stones;
+
+    while let Some(start) = unvisited.first() {
+        let chain = Bitboard::from_point(start).king_flood_fill(unvisited);
+        unvisited -= chain;
+
+        if chain.count() >= rules.min_region_stones {
+            walls |= chain;
+        }
+    }
+
+    walls
+}
+
+/// Area enclosed by `stones` on a board of `config` size.
+pub fn enclosure(config: BoardConfig, rules: &RuleSet, stones: Bitboard) -> Enclosure {
+    let board = config.squares();
+    let walls = wall_stones(rules, stones);
+
+    // Stone (i, j) is the top right corner of square (i, j). Walls are indexed by the square
+    // they cross, or by the square to the left of / below the edge they run along.
+    let slash = walls.shift(1, 1) & walls;
+    let backslash = walls.shift(1, 0) & walls.shift(0, 1);
+    let right_edge = walls.shift(0, 1) & walls;
+    let top_edge = walls.shift(1, 0) & walls;
+
+    // triangles reachable from outside of the board
+    let mut north = Bitboard::row(config.size - 1) & board;
+    let mut east = Bitboard::column(config.size - 1) & board;
+    let mut south = Bitboard::row(0) & board;
+    let mut west = Bitboard::column(0) & board;
+
+    loop {
+        let next_north =
+            north | (east - slash) | (west - backslash) | (south.shift(0, -1) - top_edge);
+        let next_east =
+            east | (north - slash) | (south - backslash) | (west.shift(-1, 0) - right_edge);
+        let next_south =
+            south | (east - backslash) | (west - slash) | (north - top_edge).shift(0, 1);
+        let next_west =
+            west | (south - slash) | (north - backslash) | (east - right_edge).shift(1, 0);
+
+        let next = [
+            next_north & board,
+            next_east & board,
+            next_south & board,
+            next_west & board,
+        ];
+        if next == [north, east, south, west] {
+            break;
+        }
+        [north, east, south, west] = next;
+    }
+
+    let (north, east, south, west) = (board - north, board - east, board - south, board - west);
+    let territory = (north & east & south)
+        | (north & east & west)
+        | (north & south & west)
+        | (east & south & west);
+
+    // north triangle of square (i, j) touches intersection (i, j)
+    let intersections = (config.intersections() & north) - walls;
+
+    Enclosure {
+        territory,
+        intersections,
+    }
+}
@@ -0,0 +1,153 @@
+//! Training data from self-play, for learning an evaluation offline.
+//!
+//! Every position of a self-play game becomes a [`Sample`]: the pieces as planes seen from the
+//! side to move, the phase, the move played with the visit counts of the search, and how the
+//! game ended for the side to move. Samples are written one JSON object per line.
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitboard::Bitboard,
+    board::{BoardState, Move},
+    logic::{GameLogic, GamePhase, GameResult, Side},
+    mcts::{self, MctsConfig, MoveStats},
+};
+
+/// Pieces of one kind for the side to move and the opponent, in that order.
+///
+/// A plane is a hex string of one bit per point, most significant bit first and padded to whole
+/// bytes. Point (i, j) is bit `j * width + i`, where squares are `size` wide and stone
+/// intersections `size - 1` wide.
+pub type Planes = [String; 2];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub size: usize,
+    pub phase: GamePhase,
+    /// Side to move, the planes are seen from it.
+    pub side: Side,
+    /// Step of the turn structure.
+    pub step: usize,
+    /// Draught in the middle of a chain of takeovers.
+    pub chain: Option<(usize, usize)>,
+    pub draughts: Planes,
+    pub stones: Planes,
+    pub watchtowers: Planes,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub position: Position,
+    #[serde(rename = "move")]
+    pub mv: Move,
+    /// Moves the search tried with their visits, empty when there was only one move.
+    pub search: Vec<MoveStats>,
+    /// Result for the side to move: 1 is a win, 0.5 a draw and 0 a loss.
+    pub result: f32,
+    /// Final score of the side to move minus the opponent's.
+    pub margin: i32,
+}
+
+fn plane(points: Bitboard, width: usize) -> String {
+    let mut bytes = vec![0u8; (width * width).div_ceil(8)];
+    for (i, j) in points.iter() {
+        let n = j * width + i;
+        bytes[n / 8] |= 0x80 >> (n % 8);
+    }
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+impl Position {
+    pub fn new(board: &BoardState) -> Self {
+        let side: Side = board.turn.into();
+        let size = board.config.size;
+        let armies = [board.army(side), board.army(side.opposite())];
+        let watchtower = |n: usize| {
+            let mut points = Bitboard::default();
+            if let Some(watchtower) = armies[n].watchtower {
+                points.insert(watchtower);
+            }
+            plane(points, size)
+        };
+
+        Position {
+            size,
+            phase: board.phase,
+            side,
+            step: board.step,
+            chain: board.chain,
+            draughts: [0, 1].map(|n| plane(armies[n].draughts, size)),
+            stones: [0, 1].map(|n| plane(armies[n].stones, size - 1)),
+            watchtowers: [0, 1].map(watchtower),
+        }
+    }
+}
+
+/// Plays games of MCTS against itself and records their positions.
+pub struct SelfPlay {
+    pub config: MctsConfig,
+    /// Moves at the start of a game picked in proportion to their visits rather than the most
+    /// visited one, so games don't all go the same way.
+    pub random_moves: usize,
+    rng: StdRng,
+}
+
+impl SelfPlay {
+    pub fn new(config: MctsConfig, random_moves: usize) -> Self {
+        SelfPlay {
+            config,
+            random_moves,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Plays `game` to the end and returns the finished game with a sample of each position.
+    pub fn play(&mut self, mut game: GameLogic) -> (GameLogic, Vec<Sample>) {
+        let mut positions = vec![];
+        while game.result().is_none() {
+            let result = mcts::search(&game, &self.config);
+            let mv = if positions.len() < self.random_moves && !result.moves.is_empty() {
+                let visits = WeightedIndex::new(result.moves.iter().map(|stats| stats.visits));
+                match visits {
+                    Ok(visits) => result.moves[visits.sample(&mut self.rng)].mv,
+                    Err(_) => result.best_move.unwrap_or(Move::Resign),
+                }
+            } else {
+                result.best_move.unwrap_or(Move::Resign)
+            };
+
+            positions.push((Position::new(game.board()), mv, result.moves));
+            game.apply(mv).unwrap();
+        }
+
+        let game_result = game.result().unwrap();
+        let score = game.score();
+        let samples = positions
+            .into_iter()
+            .map(|(position, mv, search)| Sample {
+                result: match game_result {
+                    GameResult::Winner(side) if side == position.side => 1.,
+                    GameResult::Winner(_) => 0.,
+                    GameResult::Draw => 0.5,
+                },
+                margin: score.margin(position.side),
+                position,
+                mv,
+                search,
+            })
+            .collect();
+        (game, samples)
+    }
+}
+
+impl Sample {
+    /// The sample as one line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("samples are plain data")
+    }
+
+    pub fn from_json(line: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(line)
+    }
+}
@@ -0,0 +1,56 @@
+//! Order of actions within a turn.
+//!
+//! After both watchtowers are placed every turn is a list of steps. Each step allows one or
+//! more actions and the player does exactly one of them, so `[[MoveDraught], [PlaceStone]]`
+//! moves a draught and then places a stone, and `[[MoveDraught, PlaceStone]]` does either one.
+
+use serde::{Deserialize, Serialize};
+
+use crate::logic::GamePhase;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// Step, take over or nuke with a draught.
+    MoveDraught,
+    PlaceStone,
+}
+
+/// Steps of a turn, in the order they are played.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TurnStructure(pub Vec<Vec<Action>>);
+
+impl Default for TurnStructure {
+    fn default() -> Self {
+        TurnStructure(vec![vec![Action::MoveDraught], vec![Action::PlaceStone]])
+    }
+}
+
+impl TurnStructure {
+    /// A turn needs at least one step and every step at least one action.
+    pub fn is_valid(&self) -> bool {
+        !self.0.is_empty() && self.0.iter().all(|actions| !actions.is_empty())
+    }
+
+    pub fn n_steps(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn allows(&self, step: usize, action: Action) -> bool {
+        self.0
+            .get(step)
+            .is_some_and(|actions| actions.contains(&action))
+    }
+
+    /// Phase the game is in during `step`.
+    pub fn phase(&self, step: usize) -> GamePhase {
+        match (
+            self.allows(step, Action::MoveDraught),
+            self.allows(step, Action::PlaceStone),
+        ) {
+            (true, true) => GamePhase::ChooseAction,
+            (false, true) => GamePhase::PlaceGoPiece,
+            _ => GamePhase::MoveDraught,
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
 
-use crate::{game::*, logic::*};
+use crate::{board::Move, events::*, game::*, logic::*, players::Players};
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.35);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
-const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+pub(crate) const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.35);
+pub(crate) const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub(crate) const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
 
 #[derive(Component)]
 pub struct NextMoveText;
@@ -14,7 +14,11 @@ pub struct NextMoveText;
 pub struct ButtonNukeDraught;
 
 #[derive(Component)]
-pub struct GameOverText;
+pub struct ButtonEndChain;
+
+/// Plays its move for the side to move: passing, resigning or draws.
+#[derive(Component)]
+pub struct ButtonGameAction(pub Move);
 
 #[derive(Component)]
 pub struct GameStatsText;
@@ -53,40 +57,31 @@ pub fn init_stats_text(mut commands: Commands) {
         .insert(Pickable::IGNORE);
 }
 
-pub fn show_stats(
-    mut q_draughts: Query<(Entity, &mut Transform, &mut Draught)>,
-    mut q_stones: Query<&Stone>,
-    game_logic: ResMut<GameLogic>,
-    mut text_query: Query<(&mut Text, &GameStatsText)>,
-) {
-    let black_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::Black)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_draughts = q_draughts
-        .iter_mut()
-        .filter(|(_, _, draught)| draught.side == Side::White)
-        .map(|(_, _, draught)| (draught.i, draught.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let black_stones = q_stones
-        .iter_mut()
-        .filter(|go_piece| go_piece.side == Side::Black)
-        .map(|go_piece| (go_piece.i, go_piece.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let white_stones = q_stones
-        .iter_mut()
-        .filter(|go_piece| go_piece.side == Side::White)
-        .map(|go_piece| (go_piece.i, go_piece.j))
-        .collect::<Vec<(usize, usize)>>();
-
-    let stats = game_logic.stats(black_draughts, white_draughts, white_stones, black_stones);
+pub fn show_stats(game_logic: Res<GameLogic>, mut text_query: Query<(&mut Text, &GameStatsText)>) {
+    let stats = game_logic.stats();
+    let score = game_logic.score();
+
+    let mut value = format!("Move {}", stats.n_moves);
+    if let Some(moves_left) = game_logic.moves_left() {
+        value += &format!(", {moves_left} left");
+    }
+    for (name, side) in [("White", Side::White), ("Black", Side::Black)] {
+        let side_stats = stats.side(side);
+        value += &format!(
+            "\n{name}: {} points\n  territory {}, draughts {}, captured {}",
+            score.side(side),
+            side_stats.territory,
+            side_stats.draughts,
+            side_stats.captured_draughts,
+        );
+    }
+    value += &match score.leader() {
+        Some(side) => format!("\n{:?} leads by {}", side, score.margin(side)),
+        None => "\nScores are level".into(),
+    };
 
     for (mut text, _tag) in text_query.iter_mut() {
-        text.sections[0].value = format!("{:?}", stats);
+        text.sections[0].value = value.clone();
     }
 }
 
@@ -125,80 +120,213 @@ pub fn init_buttons(mut commands: Commands) {
             ));
         })
         .insert(Pickable::IGNORE);
+
+    // stops an optional chain of takeovers, right under the nuke button
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(75.0),
+                    width: Val::Px(170.0),
+                    height: Val::Px(65.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            ButtonEndChain,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    "Stop Jumping",
+                    TextStyle {
+                        font_size: 30.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(Pickable::IGNORE);
+
+    // moves that aren't made with pieces, along the bottom of the screen
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                column_gap: Val::Px(10.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (label, mv) in [
+                ("Pass", Move::Pass),
+                ("Offer Draw", Move::OfferDraw),
+                ("Accept Draw", Move::AcceptDraw),
+                ("Resign", Move::Resign),
+            ] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(170.0),
+                                height: Val::Px(65.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: NORMAL_BUTTON.into(),
+                            visibility: Visibility::Hidden,
+                            ..Default::default()
+                        },
+                        ButtonGameAction(mv),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(
+                                label,
+                                TextStyle {
+                                    font_size: 30.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                    ..Default::default()
+                                },
+                            ),
+                            ..Default::default()
+                        });
+                    });
+            }
+        })
+        .insert(Pickable::IGNORE);
+}
+
+/// Shows the buttons whose moves the side to move can play, when it's played from here.
+/// Passing a chain of takeovers is left to the "Stop Jumping" button.
+pub fn show_game_action_buttons(
+    game_logic: Res<GameLogic>,
+    players: Res<Players>,
+    mut q_buttons: Query<(&ButtonGameAction, &mut Visibility)>,
+) {
+    let board = game_logic.board();
+    let side: Side = board.turn.into();
+    let playing = players.side(side).is_human()
+        && matches!(
+            board.phase,
+            GamePhase::PlaceWatchtower
+                | GamePhase::PlaceGoPiece
+                | GamePhase::MoveDraught
+                | GamePhase::ChooseAction
+        );
+
+    for (ButtonGameAction(mv), mut visibility) in q_buttons.iter_mut() {
+        let visible = playing
+            && match mv {
+                Move::Pass => board.chain.is_none() && board.legal_moves().contains(&Move::Pass),
+                Move::OfferDraw => game_logic.draw_offer().is_none(),
+                Move::AcceptDraw => game_logic.draw_offer() == Some(side.opposite()),
+                _ => true,
+            };
+        *visibility = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub fn game_action_button_system(
+    mut interaction_query: Query<
+        (&ButtonGameAction, &Interaction, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut game_logic: ResMut<GameLogic>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
+) {
+    for (ButtonGameAction(mv), interaction, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                play_move(*mv, &mut game_logic, &mut ew_move_played);
+                *color = PRESSED_BUTTON.into();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+pub fn end_chain_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut Visibility),
+        (Changed<Interaction>, With<ButtonEndChain>),
+    >,
+    mut selected_draught: ResMut<SelectedDraught>,
+    mut game_logic: ResMut<GameLogic>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
+) {
+    for (interaction, mut color, mut visibility) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if play_move(Move::Pass, &mut game_logic, &mut ew_move_played) {
+                    selected_draught.n = None;
+                    *visibility = Visibility::Hidden;
+                }
+
+                *color = PRESSED_BUTTON.into();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
 }
 
-#[allow(clippy::type_complexity)]
 pub fn nuke_draught_button_system(
-    mut commands: Commands,
     mut interaction_query: Query<
         (&ButtonNukeDraught, &Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>),
     >,
-    q_draughts: Query<(Entity, &mut Draught)>,
-    q_stones: Query<(Entity, &Stone)>,
-    q_watchtowers: Query<(Entity, &Watchtower)>,
+    q_draughts: Query<&Draught>,
     mut selected_draught: ResMut<SelectedDraught>,
     mut q_nuke_draught_button: Query<(Entity, &mut Visibility, &ButtonNukeDraught)>,
-    mut turn: ResMut<Turn>,
+    turn: Res<Turn>,
     mut game_logic: ResMut<GameLogic>,
-
-    mut game_phase: ResMut<NextState<GamePhase>>,
+    mut ew_move_played: EventWriter<EventMovePlayed>,
 ) {
-    let turn_ = *turn;
-    let side = match turn_ {
-        Turn::Black => Side::Black,
-        Turn::White => Side::White,
-    };
+    let side: Side = (*turn).into();
 
     for (_, interaction, mut color) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
-                let draught = q_draughts
-                    .iter()
-                    .find(|d| d.1.n == selected_draught.n.unwrap() && d.1.side == side)
-                    .unwrap()
-                    .1;
-
-                let ccs_to_remove: Vec<(i32, i32)> = vec![
-                    (draught.i as i32 - 1, draught.j as i32 - 1),
-                    (draught.i as i32 - 1, draught.j as i32),
-                    (draught.i as i32 - 1, draught.j as i32 + 1),
-                    (draught.i as i32, draught.j as i32 - 1),
-                    (draught.i as i32, draught.j as i32),
-                    (draught.i as i32, draught.j as i32 + 1),
-                    (draught.i as i32 + 1, draught.j as i32 - 1),
-                    (draught.i as i32 + 1, draught.j as i32),
-                    (draught.i as i32 + 1, draught.j as i32 + 1),
-                ];
-
-                let ccs_to_remove: Vec<_> = ccs_to_remove
-                    .iter()
-                    .filter(|(i, j)| *i >= 0 && *j >= 0)
-                    .collect();
-
-                for (e, d) in q_draughts.iter() {
-                    for cc in ccs_to_remove.iter() {
-                        if d.i as i32 == cc.0 && d.j as i32 == cc.1 {
-                            commands.entity(e).despawn_recursive();
-                        }
-                    }
-                }
+                let Some(n) = selected_draught.n else {
+                    return;
+                };
+                let Some(draught) = q_draughts.iter().find(|d| d.n == n && d.side == side) else {
+                    return;
+                };
 
-                for (e, d) in q_stones.iter() {
-                    for cc in ccs_to_remove.iter() {
-                        if d.i as i32 == cc.0 && d.j as i32 == cc.1 {
-                            commands.entity(e).despawn_recursive();
-                        }
-                    }
-                }
-
-                for (e, d) in q_watchtowers.iter() {
-                    for cc in ccs_to_remove.iter() {
-                        if d.i as i32 == cc.0 && d.j as i32 == cc.1 {
-                            commands.entity(e).despawn_recursive();
-                        }
-                    }
-                }
+                play_move(
+                    Move::Nuke {
+                        i: draught.i,
+                        j: draught.j,
+                    },
+                    &mut game_logic,
+                    &mut ew_move_played,
+                );
 
                 selected_draught.n = None;
 
@@ -207,11 +335,6 @@ pub fn nuke_draught_button_system(
                 }
 
                 *color = PRESSED_BUTTON.into();
-
-                game_logic.log(GamePhase::MoveDraught, turn_);
-                let (next_phase, next_turn) = game_logic.next_state();
-                *turn = next_turn;
-                game_phase.set(next_phase);
                 return;
             }
             Interaction::Hovered => {
@@ -224,6 +347,31 @@ pub fn nuke_draught_button_system(
     }
 }
 
+/// Forgets the selected draught once a move is played, the nuke button would otherwise act on
+/// a draught of the next turn.
+pub fn clear_draught_selection(
+    mut er_move_played: EventReader<EventMovePlayed>,
+    game_logic: Res<GameLogic>,
+    mut selected_draught: ResMut<SelectedDraught>,
+    mut q_nuke_draught_button: Query<&mut Visibility, With<ButtonNukeDraught>>,
+) {
+    // a draw offer leaves the player in the middle of their step
+    if er_move_played
+        .read()
+        .all(|EventMovePlayed(mv, _)| *mv == Move::OfferDraw)
+    {
+        return;
+    }
+    // so does a takeover in a chain, the draught stays selected to jump on
+    if game_logic.board().chain.is_some() {
+        return;
+    }
+    selected_draught.n = None;
+    for mut v in q_nuke_draught_button.iter_mut() {
+        *v = Visibility::Hidden;
+    }
+}
+
 pub fn init_game_over_text(mut commands: Commands) {
     let text = Text::from_section(
         "GAME OVER",
@@ -256,3 +404,66 @@ pub fn init_game_over_text(mut commands: Commands) {
         })
         .insert(Pickable::IGNORE);
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::board::BoardState;
+
+    /// The draught in the corner can take over two black draughts in a row.
+    const TWO_IN_A_ROW: &str = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . b . . . . .
+
+. . . . . . . . .
+
+. b . . . . . . .
+
+w . . . . . . . w
+White to move, MoveDraught";
+
+    /// Plays `mv` with the corner draught selected, then clears the selection as after a move.
+    fn selection_after(mv: Move) -> Option<usize> {
+        let mut world = World::new();
+        world.init_resource::<Events<EventMovePlayed>>();
+        world.insert_resource(GameLogic::from_board(
+            BoardState::from_ascii(TWO_IN_A_ROW).unwrap(),
+        ));
+        world.insert_resource(SelectedDraught { n: Some(0) });
+        world.spawn((ButtonNukeDraught, Visibility::Visible));
+
+        let outcome = world.resource_mut::<GameLogic>().apply(mv).unwrap();
+        world.send_event(EventMovePlayed(mv, outcome));
+        world.run_system_once(clear_draught_selection);
+        world.resource::<SelectedDraught>().n
+    }
+
+    #[test]
+    fn selection_survives_a_takeover_in_a_chain() {
+        let mv = Move::TakeOverDraught {
+            from: (0, 0),
+            to: (2, 2),
+        };
+        assert_eq!(selection_after(mv), Some(0));
+    }
+
+    #[test]
+    fn selection_is_cleared_after_a_step() {
+        let mv = Move::StepDraught {
+            from: (0, 0),
+            to: (1, 0),
+        };
+        assert_eq!(selection_after(mv), None);
+    }
+}
@@ -0,0 +1,112 @@
+//! Zobrist hashing of positions.
+//!
+//! Every piece on every point has a fixed random key and a position hashes to the xor of the
+//! keys of everything on the board, and of the number of draughts each side took over. The keys are generated at compile time from a fixed seed,
+//! so hashes are the same in every build and can be compared between replays and over network.
+
+use crate::{
+    bitboard::Bitboard,
+    board::BoardState,
+    logic::{GamePhase, Side, Turn, MAX_BOARD_SIZE},
+};
+
+const POINTS: usize = MAX_BOARD_SIZE * MAX_BOARD_SIZE;
+
+/// Kinds of pieces for each side: draughts, stones and watchtowers.
+const PIECES: usize = 3;
+const PHASES: usize = 6;
+const STEPS: usize = 8;
+const CAPTURES: usize = POINTS;
+
+static KEYS: [u64; (2 * PIECES + 1) * POINTS + PHASES + 1 + STEPS + 2 * CAPTURES] = keys();
+
+/// splitmix64, good enough to spread the keys and usable in a const fn.
+const fn keys<const N: usize>() -> [u64; N] {
+    let mut keys = [0; N];
+    let mut state: u64 = 0x5761_7463_6874_6f77;
+    let mut n = 0;
+    while n < N {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[n] = z ^ (z >> 31);
+        n += 1;
+    }
+    keys
+}
+
+fn piece_key(side: Side, piece: usize, (i, j): (usize, usize)) -> u64 {
+    let side = match side {
+        Side::White => 0,
+        Side::Black => 1,
+    };
+    KEYS[(side * PIECES + piece) * POINTS + j * MAX_BOARD_SIZE + i]
+}
+
+/// Draught in the middle of a chain of takeovers.
+fn chain_key((i, j): (usize, usize)) -> u64 {
+    KEYS[2 * PIECES * POINTS + j * MAX_BOARD_SIZE + i]
+}
+
+fn phase_key(phase: GamePhase) -> u64 {
+    let phase = match phase {
+        GamePhase::Initialize => 0,
+        GamePhase::PlaceWatchtower => 1,
+        GamePhase::PlaceGoPiece => 2,
+        GamePhase::MoveDraught => 3,
+        GamePhase::ChooseAction => 4,
+        GamePhase::GameOver => 5,
+    };
+    KEYS[(2 * PIECES + 1) * POINTS + phase]
+}
+
+fn black_to_move_key() -> u64 {
+    KEYS[(2 * PIECES + 1) * POINTS + PHASES]
+}
+
+/// Step of the turn, turns longer than `STEPS` share keys.
+fn step_key(step: usize) -> u64 {
+    KEYS[(2 * PIECES + 1) * POINTS + PHASES + 1 + step % STEPS]
+}
+
+/// Draughts taken over by `side`, they count towards the score.
+fn captures_key(side: Side, captures: usize) -> u64 {
+    let side = match side {
+        Side::White => 0,
+        Side::Black => 1,
+    };
+    KEYS[(2 * PIECES + 1) * POINTS + PHASES + 1 + STEPS + side * CAPTURES + captures % CAPTURES]
+}
+
+/// Hash of the pieces, the side to move, the phase, the step of the turn, the chain of
+/// takeovers and the captured draughts of `board`.
+pub fn hash(board: &BoardState) -> u64 {
+    let mut hash = phase_key(board.phase);
+    if board.turn == Turn::Black {
+        hash ^= black_to_move_key();
+    }
+    if let Some(chain) = board.chain {
+        hash ^= chain_key(chain);
+    }
+    hash ^= step_key(board.step);
+
+    for side in [Side::White, Side::Black] {
+        let army = board.army(side);
+        if army.captured_draughts > 0 {
+            hash ^= captures_key(side, army.captured_draughts);
+        }
+        let pieces = [
+            army.draughts,
+            army.stones,
+            army.watchtower.into_iter().collect::<Bitboard>(),
+        ];
+        for (piece, points) in pieces.iter().enumerate() {
+            for point in points.iter() {
+                hash ^= piece_key(side, piece, point);
+            }
+        }
+    }
+
+    hash
+}
@@ -0,0 +1,77 @@
+//! Board diagrams read back into the same position.
+
+use watchtower_defence::{
+    board::{BoardState, Move},
+    logic::{BoardConfig, GamePhase, Side, Turn},
+    rules::RuleSet,
+};
+
+#[test]
+fn played_position_round_trips() {
+    let mut board = BoardState::with_rules(BoardConfig::new(13), RuleSet::default());
+    for mv in [
+        Move::PlaceWatchtower { i: 3, j: 3 },
+        Move::PlaceWatchtower { i: 9, j: 9 },
+        Move::StepDraught {
+            from: (4, 4),
+            to: (5, 5),
+        },
+        Move::PlaceStone { i: 6, j: 6 },
+    ] {
+        board.apply(mv).unwrap();
+    }
+
+    let text = board.to_string();
+    assert_eq!(BoardState::from_ascii(&text).unwrap(), board, "{text}");
+}
+
+#[test]
+fn reads_diagram() {
+    let text = "\
+# White tower in the bottom left corner
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+           X
+. . . . . . b . .
+
+. w . . . . . . .
+ O O
+. W . . . . . . .
+   O
+. . . . . . . . .
+Black to move, PlaceGoPiece";
+
+    let board = BoardState::from_ascii(text).unwrap();
+    assert_eq!(board.config.size, 9);
+    assert_eq!(
+        (board.turn, board.phase),
+        (Turn::Black, GamePhase::PlaceGoPiece)
+    );
+    assert_eq!(board.white.watchtower, Some((1, 1)));
+    assert_eq!(board.black.watchtower, None);
+    assert!(board.white.draughts.contains((1, 2)));
+    assert!(board.black.draughts.contains((6, 3)));
+    assert_eq!(board.white.stones.count(), 3);
+    assert!(board.white.stones.contains((1, 0)));
+    assert!(board.black.stones.contains((5, 3)));
+    assert_eq!(board.army(Side::Black).stones.count(), 1);
+    assert_eq!(board.to_string(), text.split_once('\n').unwrap().1);
+}
+
+#[test]
+fn rejects_bad_diagrams() {
+    let empty = [". . . . . . . . ."; 9].join("\n\n");
+    assert!(BoardState::from_ascii(&empty).is_ok());
+
+    assert!(BoardState::from_ascii(". . .\n\n. . .").is_err());
+    assert!(BoardState::from_ascii(&empty.replacen(". .", ". ?", 1)).is_err());
+    assert!(BoardState::from_ascii(&empty.replacen(". . .", "W . W", 1)).is_err());
+    assert!(BoardState::from_ascii(&(empty + "\nWhite to move, Sleeping")).is_err());
+}
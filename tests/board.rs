@@ -0,0 +1,468 @@
+//! Rules of single moves played on a `BoardState`.
+
+use watchtower_defence::{
+    board::{BoardState, Move, MoveError},
+    logic::{BoardConfig, GamePhase, Side, Turn},
+    rules::{ChainedTakeovers, RuleSet},
+};
+
+// ----
+// Nuke
+// ----
+
+#[test]
+fn nuke_is_clipped_at_the_edge() {
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+     X
+. . b . . . . . .
+   X
+. b . . . . . . .
+ X O
+w . . . . . . . .
+White to move, MoveDraught";
+    let board = BoardState::from_ascii(text).unwrap();
+
+    let blast = board.blast((0, 0));
+    assert_eq!(blast.squares.count(), 4);
+    for square in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        assert!(blast.squares.contains(square), "{square:?}");
+    }
+    assert_eq!(blast.intersections.count(), 4);
+    for intersection in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        assert!(
+            blast.intersections.contains(intersection),
+            "{intersection:?}"
+        );
+    }
+}
+
+#[test]
+fn nuke_clears_both_sides_in_the_blast() {
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+     X
+. . b . . . . . .
+   X
+. b . . . . . . .
+ X O
+w . . . . . . . .
+White to move, MoveDraught";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    board.apply(Move::Nuke { i: 0, j: 0 }).unwrap();
+
+    // the nuked draught and the white stone go too
+    assert!(board.white.draughts.is_empty());
+    assert!(board.white.stones.is_empty());
+    assert_eq!(board.black.draughts.iter().collect::<Vec<_>>(), [(2, 2)]);
+    assert_eq!(board.black.stones.iter().collect::<Vec<_>>(), [(2, 2)]);
+    assert_eq!(board.phase, GamePhase::PlaceGoPiece);
+}
+
+#[test]
+fn nuke_needs_a_draught_of_the_side_to_move() {
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+     X
+. . b . . . . . .
+   X
+. b . . . . . . .
+ X O
+w . . . . . . . .
+White to move, MoveDraught";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    assert_eq!(
+        board.apply(Move::Nuke { i: 1, j: 1 }).err(),
+        Some(MoveError::NoDraught)
+    );
+    assert_eq!(
+        board.apply(Move::Nuke { i: 4, j: 4 }).err(),
+        Some(MoveError::NoDraught)
+    );
+
+    board
+        .apply(Move::StepDraught {
+            from: (0, 0),
+            to: (1, 0),
+        })
+        .unwrap();
+    assert_eq!(board.turn, Turn::White);
+    assert_eq!(
+        board.apply(Move::Nuke { i: 1, j: 0 }).err(),
+        Some(MoveError::WrongPhase)
+    );
+}
+
+// ------
+// Stones
+// ------
+
+#[test]
+fn stone_needs_a_free_intersection_on_the_board() {
+    // Black encloses a square with a diamond of stones, which White can break.
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . B .
+         O
+. . . . . . . . .
+       O X O
+. . . . . . . . .
+       X   X
+. . . . . . . . .
+         X
+. . . . . . . . .
+
+. . . . . . . . .
+
+. W . . . . . . .
+
+. . . . . . . . .
+White to move, PlaceGoPiece";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    assert_eq!(
+        board.apply(Move::PlaceStone { i: 8, j: 0 }).err(),
+        Some(MoveError::OutOfBoard)
+    );
+    assert_eq!(
+        board.apply(Move::PlaceStone { i: 4, j: 5 }).err(),
+        Some(MoveError::Occupied)
+    );
+    assert!(board.apply(Move::PlaceStone { i: 0, j: 0 }).is_ok());
+}
+
+#[test]
+fn stone_breaking_an_enclosure_captures() {
+    // Black encloses a square with a diamond of stones, which White can break.
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . B .
+         O
+. . . . . . . . .
+       O X O
+. . . . . . . . .
+       X   X
+. . . . . . . . .
+         X
+. . . . . . . . .
+
+. . . . . . . . .
+
+. W . . . . . . .
+
+. . . . . . . . .
+White to move, PlaceGoPiece";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    assert!(board.legal_stone_placements(Turn::White).contains((4, 4)));
+
+    let outcome = board.apply(Move::PlaceStone { i: 4, j: 4 }).unwrap();
+    assert_eq!(outcome.removed_stones, [(Side::Black, (4, 5))]);
+    assert!(!board.black.stones.contains((4, 5)));
+}
+
+#[test]
+fn stone_cant_go_into_enemy_territory() {
+    // Same diamond inside a wider black ring, which still encloses it after a capture.
+    let text = "\
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . B .
+
+. . . . . . . . . . . . .
+             X
+. . . . . . . . . . . . .
+           X O X
+. . . . . . . . . . . . .
+         X O X O X
+. . . . . . . . . . . . .
+       X   X   X   X
+. . . . . . . . . . . . .
+         X   X   X
+. . . . . . . . . . . . .
+           X   X
+. . . . . . . . . . . . .
+             X
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. W . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+White to move, PlaceGoPiece";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    assert!(!board.legal_stone_placements(Turn::White).contains((5, 5)));
+    assert_eq!(
+        board.apply(Move::PlaceStone { i: 5, j: 5 }).err(),
+        Some(MoveError::EnemyTerritory)
+    );
+}
+
+#[test]
+fn stone_cant_capture_itself() {
+    // Same diamond inside a wider black ring, which still encloses it after a capture.
+    let text = "\
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . B .
+
+. . . . . . . . . . . . .
+             X
+. . . . . . . . . . . . .
+           X O X
+. . . . . . . . . . . . .
+         X O X O X
+. . . . . . . . . . . . .
+       X   X   X   X
+. . . . . . . . . . . . .
+         X   X   X
+. . . . . . . . . . . . .
+           X   X
+. . . . . . . . . . . . .
+             X
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. W . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+White to move, PlaceGoPiece";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    let before = board.clone();
+    assert!(!board.legal_stone_placements(Turn::White).contains((6, 6)));
+    assert_eq!(
+        board.apply(Move::PlaceStone { i: 6, j: 6 }).err(),
+        Some(MoveError::SelfCapture)
+    );
+    assert_eq!(board, before);
+}
+
+// ----------
+// Watchtower
+// ----------
+
+fn empty_board(size: usize) -> BoardState {
+    BoardState::with_rules(BoardConfig::new(size), RuleSet::default())
+}
+
+#[test]
+fn watchtower_keeps_off_the_edge() {
+    let mut board = empty_board(13);
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 1, j: 6 }).err(),
+        Some(MoveError::OutOfBoard)
+    );
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 6, j: 10 }).err(),
+        Some(MoveError::OutOfBoard)
+    );
+    assert!(board.apply(Move::PlaceWatchtower { i: 3, j: 3 }).is_ok());
+}
+
+#[test]
+fn watchtower_keeps_away_from_the_enemy_one() {
+    let mut board = empty_board(13);
+    board.apply(Move::PlaceWatchtower { i: 3, j: 3 }).unwrap();
+    assert_eq!(board.turn, Turn::Black);
+
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 6, j: 6 }).err(),
+        Some(MoveError::TooCloseToWatchtower)
+    );
+    assert!(!board.legal_tower_placements(Side::Black).contains((6, 6)));
+
+    // exactly the minimum distance away is fine
+    assert!(board.legal_tower_placements(Side::Black).contains((3, 8)));
+    board.apply(Move::PlaceWatchtower { i: 3, j: 8 }).unwrap();
+    assert_eq!(board.black.watchtower, Some((3, 8)));
+    assert_ne!(board.phase, GamePhase::PlaceWatchtower);
+
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 9, j: 9 }).err(),
+        Some(MoveError::WrongPhase)
+    );
+}
+
+#[test]
+fn first_watchtower_leaves_room_for_the_second() {
+    let mut board = empty_board(9);
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 4, j: 4 }).err(),
+        Some(MoveError::NoRoomForEnemyWatchtower)
+    );
+    assert!(board.apply(Move::PlaceWatchtower { i: 2, j: 2 }).is_ok());
+}
+
+#[test]
+fn watchtower_cant_land_on_enemy_pieces() {
+    let text = "\
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . w . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . W . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+
+. . . . . . . . . . . . .
+Black to move, PlaceWatchtower";
+    let mut board = BoardState::from_ascii(text).unwrap();
+    assert_eq!(
+        board.apply(Move::PlaceWatchtower { i: 9, j: 9 }).err(),
+        Some(MoveError::WatchtowerBlocked)
+    );
+    assert!(board.apply(Move::PlaceWatchtower { i: 9, j: 6 }).is_ok());
+}
+
+// ------
+// Chains
+// ------
+
+/// The draught in the corner takes over two black draughts in a row.
+const TWO_IN_A_ROW: &str = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . b . . . . .
+
+. . . . . . . . .
+
+. b . . . . . . .
+
+w . . . . . . . w
+White to move, MoveDraught";
+
+fn in_a_chain(chained_takeovers: ChainedTakeovers) -> BoardState {
+    let mut board = BoardState::from_ascii(TWO_IN_A_ROW).unwrap();
+    board.rules.chained_takeovers = chained_takeovers;
+    board
+        .apply(Move::TakeOverDraught {
+            from: (0, 0),
+            to: (2, 2),
+        })
+        .unwrap();
+    board
+}
+
+#[test]
+fn chain_only_goes_on_from_the_last_square() {
+    let mut board = in_a_chain(ChainedTakeovers::Compulsory);
+    assert_eq!(board.chain, Some((2, 2)));
+    assert_eq!(board.phase, GamePhase::MoveDraught);
+    assert_eq!(
+        board.legal_moves(),
+        [Move::TakeOverDraught {
+            from: (2, 2),
+            to: (4, 4),
+        }]
+    );
+    assert_eq!(
+        board
+            .apply(Move::StepDraught {
+                from: (8, 0),
+                to: (7, 0),
+            })
+            .err(),
+        Some(MoveError::ChainInProgress)
+    );
+    assert_eq!(
+        board.apply(Move::Nuke { i: 2, j: 2 }).err(),
+        Some(MoveError::ChainInProgress)
+    );
+
+    // the chain ends when there is nothing left to take over
+    board
+        .apply(Move::TakeOverDraught {
+            from: (2, 2),
+            to: (4, 4),
+        })
+        .unwrap();
+    assert_eq!(board.chain, None);
+    assert_eq!(board.phase, GamePhase::PlaceGoPiece);
+    assert_eq!(board.white.draughts.count(), 4);
+    assert!(board.black.draughts.is_empty());
+}
+
+#[test]
+fn compulsory_chain_cant_be_stopped() {
+    let mut board = in_a_chain(ChainedTakeovers::Compulsory);
+    assert!(!board.legal_moves().contains(&Move::Pass));
+    assert_eq!(board.apply(Move::Pass).err(), Some(MoveError::CannotPass));
+    assert_eq!(board.chain, Some((2, 2)));
+}
+
+#[test]
+fn optional_chain_stops_on_a_pass() {
+    let mut board = in_a_chain(ChainedTakeovers::Optional);
+    let mut moves = board.legal_moves();
+    moves.sort_by_key(|mv| mv.to_string());
+    let mut expected = vec![
+        Move::TakeOverDraught {
+            from: (2, 2),
+            to: (4, 4),
+        },
+        Move::Pass,
+    ];
+    expected.sort_by_key(|mv| mv.to_string());
+    assert_eq!(moves, expected);
+
+    board.apply(Move::Pass).unwrap();
+    assert_eq!(board.chain, None);
+    assert_eq!(board.turn, Turn::White);
+    assert_eq!(board.phase, GamePhase::PlaceGoPiece);
+}
@@ -0,0 +1,106 @@
+//! Games ending without a watchtower falling, and their results.
+
+use watchtower_defence::{
+    board::{BoardState, Move, MoveError},
+    logic::{Ending, GameLogic, GamePhase, GameResult, Side},
+};
+
+/// White is a draught ahead.
+const WHITE_AHEAD: &str = "\
+. . . . . . . . b
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+w w . . . . . . .
+White to move, MoveDraught";
+
+fn white_ahead() -> GameLogic {
+    GameLogic::from_board(BoardState::from_ascii(WHITE_AHEAD).unwrap())
+}
+
+const BLACK_STEP: Move = Move::StepDraught {
+    from: (8, 8),
+    to: (7, 8),
+};
+
+#[test]
+fn passing_out_goes_to_the_score() {
+    let mut game = white_ahead();
+    assert_eq!(game.score().leader(), Some(Side::White));
+
+    // a board move in between starts the count again
+    for mv in [Move::Pass, Move::Pass, BLACK_STEP, Move::Pass] {
+        game.apply(mv).unwrap();
+    }
+    for _ in 0..3 {
+        assert_eq!(game.ending(), None);
+        game.apply(Move::Pass).unwrap();
+    }
+    assert_eq!(game.ending(), Some(Ending::Passes));
+    assert_eq!(game.result(), Some(GameResult::Winner(Side::White)));
+}
+
+#[test]
+fn resigning_loses() {
+    let mut game = white_ahead();
+    game.apply(Move::Resign).unwrap();
+
+    assert_eq!(game.ending(), Some(Ending::Resignation(Side::White)));
+    assert_eq!(game.result(), Some(GameResult::Winner(Side::Black)));
+    let entry = game.log().last().unwrap();
+    assert_eq!(entry.mv, Move::Resign);
+    assert_eq!(entry.phase, GamePhase::MoveDraught);
+    assert_eq!(game.apply(Move::Pass).err(), Some(MoveError::GameOver));
+}
+
+#[test]
+fn accepted_draw_offer() {
+    let mut game = white_ahead();
+    game.apply(Move::OfferDraw).unwrap();
+    assert_eq!(game.draw_offer(), Some(Side::White));
+    assert_eq!(game.result(), None);
+
+    // the offer doesn't use up the turn
+    game.apply(Move::Pass).unwrap();
+    game.apply(Move::Pass).unwrap();
+    game.apply(Move::AcceptDraw).unwrap();
+
+    assert_eq!(game.ending(), Some(Ending::Agreement));
+    assert_eq!(game.result(), Some(GameResult::Draw));
+}
+
+#[test]
+fn playing_on_declines_a_draw_offer() {
+    let mut game = white_ahead();
+    assert_eq!(
+        game.apply(Move::AcceptDraw).err(),
+        Some(MoveError::NoDrawOffer)
+    );
+
+    game.apply(Move::OfferDraw).unwrap();
+    game.apply(Move::Pass).unwrap();
+    game.apply(Move::Pass).unwrap();
+    // the offer stands while the side that made it plays on
+    assert_eq!(game.draw_offer(), Some(Side::White));
+    game.apply(BLACK_STEP).unwrap();
+
+    assert_eq!(game.draw_offer(), None);
+    game.apply(Move::Pass).unwrap();
+    assert_eq!(
+        game.apply(Move::AcceptDraw).err(),
+        Some(MoveError::NoDrawOffer)
+    );
+    assert_eq!(game.ending(), None);
+}
@@ -0,0 +1,57 @@
+//! Moves the Monte Carlo bot finds in small positions.
+
+use watchtower_defence::{
+    board::{BoardState, Move},
+    logic::{BoardConfig, GameLogic},
+    mcts::{search, MctsConfig, Rollout},
+    rules::RuleSet,
+};
+
+const CONFIG: MctsConfig = MctsConfig {
+    playouts: 300,
+    exploration: 1.4,
+    rollout: Rollout::Random,
+    rollout_steps: 10,
+    trees: Some(2),
+};
+
+#[test]
+fn nukes_enemy_watchtower() {
+    let text = "\
+# White's draught on f6 is next to Black's watchtower on g7, nuking wins on the spot.
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . B . .
+
+. . . . . w . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . b . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+White to move, MoveDraught";
+    let game = GameLogic::from_board(BoardState::from_ascii(text).unwrap());
+
+    let result = search(&game, &CONFIG);
+    assert_eq!(result.best_move, Some(Move::Nuke { i: 5, j: 5 }));
+    assert_eq!(result.moves[0].value, 1.);
+}
+
+#[test]
+fn spends_the_playout_budget() {
+    let mut game = GameLogic::with_rules(BoardConfig::new(13), RuleSet::default());
+    game.apply(Move::PlaceWatchtower { i: 3, j: 3 }).unwrap();
+    game.apply(Move::PlaceWatchtower { i: 9, j: 9 }).unwrap();
+
+    let result = search(&game, &CONFIG);
+    let visits: u32 = result.moves.iter().map(|stats| stats.visits).sum();
+    assert_eq!(visits as usize, CONFIG.playouts);
+    assert!(game.legal_moves().contains(&result.best_move.unwrap()));
+}
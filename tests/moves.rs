@@ -0,0 +1,77 @@
+//! Moves generated for the side to move, checked against what the rules allow.
+
+use watchtower_defence::{
+    board::{BoardState, Move},
+    logic::{BoardConfig, GameLogic},
+    players::{Bot, RandomBot},
+    rules::RuleSet,
+};
+
+#[test]
+fn lone_draught_in_the_corner() {
+    let text = "\
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . B . .
+
+. b . . . . . . .
+
+w . . . . . . . .
+White to move, MoveDraught";
+    let game = GameLogic::from_board(BoardState::from_ascii(text).unwrap());
+
+    let mut moves = game.legal_moves();
+    moves.sort_by_key(|mv| mv.to_string());
+    let mut expected = vec![
+        Move::StepDraught {
+            from: (0, 0),
+            to: (1, 0),
+        },
+        Move::StepDraught {
+            from: (0, 0),
+            to: (0, 1),
+        },
+        Move::TakeOverDraught {
+            from: (0, 0),
+            to: (2, 2),
+        },
+        Move::Nuke { i: 0, j: 0 },
+        Move::Pass,
+    ];
+    expected.sort_by_key(|mv| mv.to_string());
+    assert_eq!(moves, expected);
+}
+
+#[test]
+fn every_generated_move_can_be_played() {
+    let rules = RuleSet {
+        move_limit: Some(30),
+        ..Default::default()
+    };
+    for seed in 0..3 {
+        let mut game = GameLogic::with_rules(BoardConfig::new(9), rules.clone());
+        let mut bot = RandomBot::seeded(seed);
+        while game.result().is_none() {
+            for mv in game.legal_moves() {
+                if let Err(err) = game.clone().apply(mv) {
+                    panic!(
+                        "{mv} was generated but can't be played: {err}\n{}",
+                        game.board()
+                    );
+                }
+            }
+            let mv = bot.poll(&game).unwrap();
+            game.apply(mv).unwrap();
+        }
+    }
+}
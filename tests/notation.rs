@@ -0,0 +1,124 @@
+//! Move notation and written games.
+
+use watchtower_defence::{
+    board::{Move, MoveError},
+    logic::{BoardConfig, GameLogic, Side},
+    notation::{parse_intersection, parse_square, NotationError},
+    players::{Bot, RandomBot},
+    rules::RuleSet,
+};
+
+#[test]
+fn moves_round_trip() {
+    let moves = [
+        (Move::PlaceWatchtower { i: 4, j: 2 }, "Te3"),
+        (Move::PlaceStone { i: 4, j: 2 }, "SE3"),
+        (
+            Move::StepDraught {
+                from: (4, 2),
+                to: (5, 3),
+            },
+            "e3-f4",
+        ),
+        (
+            Move::TakeOverDraught {
+                from: (0, 0),
+                to: (2, 2),
+            },
+            "a1xc3",
+        ),
+        (Move::Nuke { i: 18, j: 18 }, "Ns19"),
+        (Move::PlaceStone { i: 17, j: 17 }, "SR18"),
+        (Move::Pass, "pass"),
+        (Move::Resign, "resign"),
+        (Move::OfferDraw, "offer-draw"),
+        (Move::AcceptDraw, "accept-draw"),
+    ];
+    for (mv, text) in moves {
+        assert_eq!(mv.to_string(), text);
+        assert_eq!(text.parse::<Move>(), Ok(mv));
+    }
+}
+
+#[test]
+fn rejects_bad_points() {
+    assert_eq!(parse_square("t1"), None);
+    assert_eq!(parse_square("a0"), None);
+    assert_eq!(parse_square("a20"), None);
+    assert_eq!(parse_square("a01"), None);
+    assert_eq!(parse_intersection("S1"), None);
+    assert_eq!(parse_intersection("e3"), None);
+    for text in ["", "T", "Se3", "TE3", "e3-", "e3xf", "jump"] {
+        assert_eq!(
+            text.parse::<Move>(),
+            Err(NotationError::Move(text.into())),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn random_game_round_trips() {
+    let rules = RuleSet {
+        move_limit: Some(40),
+        ..Default::default()
+    };
+    let mut game = GameLogic::with_rules(BoardConfig::new(13), rules);
+    let mut bot = RandomBot::seeded(3);
+    while game.result().is_none() {
+        let mv = bot.poll(&game).unwrap();
+        game.apply(mv).unwrap();
+    }
+
+    let text = game.to_notation();
+    let read = GameLogic::from_notation(&text).unwrap();
+    assert_eq!(read.log(), game.log());
+    assert_eq!(read.hash(), game.hash());
+    assert_eq!(read.result(), game.result());
+    assert_eq!(read.to_notation(), text);
+}
+
+#[test]
+fn reads_a_written_game() {
+    let text = "\
+[Size 9]
+[White Alice]
+# both towers first
+White: Tc3
+Black: Tg7
+White: d4-e5 SA8
+";
+    let game = GameLogic::from_notation(text).unwrap();
+    assert_eq!(game.board().config.size, 9);
+    assert_eq!(game.log().len(), 4);
+    assert_eq!(game.board().turn, Side::Black.into());
+    assert!(game.board().white.draughts.contains((4, 4)));
+    assert!(game.board().white.stones.contains((0, 7)));
+}
+
+#[test]
+fn reports_where_a_game_goes_wrong() {
+    assert_eq!(
+        GameLogic::from_notation("[Size 9]\nBlack: Tc3\n").err(),
+        Some(NotationError::WrongSide {
+            line: 2,
+            side: Side::Black
+        })
+    );
+    assert_eq!(
+        GameLogic::from_notation("[Size 9]\nWhite: Tc3\nBlack: Tc4\n").err(),
+        Some(NotationError::Illegal {
+            line: 3,
+            mv: Move::PlaceWatchtower { i: 2, j: 3 },
+            error: MoveError::TooCloseToWatchtower
+        })
+    );
+    assert!(matches!(
+        GameLogic::from_notation("[Size 7]\n"),
+        Err(NotationError::Header(_))
+    ));
+    assert!(matches!(
+        GameLogic::from_notation("[Size 9]\n[Rules (turn_structure: [])]\n"),
+        Err(NotationError::Header(_))
+    ));
+}
@@ -0,0 +1,176 @@
+//! Engine protocol lines and the reference engine.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    process::{Command as Process, Stdio},
+    time::{Duration, Instant},
+};
+
+use watchtower_defence::{
+    board::Move,
+    logic::{BoardConfig, GameLogic},
+    players::Bot,
+    protocol::{Command, ProtocolError, Reply, SubprocessBot},
+    rules::RuleSet,
+};
+
+#[test]
+fn commands_round_trip() {
+    let commands = [
+        Command::Hello,
+        Command::IsReady,
+        Command::Rules(RuleSet::default()),
+        Command::Position {
+            size: 13,
+            moves: vec![],
+        },
+        Command::Position {
+            size: 9,
+            moves: vec![
+                Move::PlaceWatchtower { i: 2, j: 2 },
+                Move::Pass,
+                Move::StepDraught {
+                    from: (1, 1),
+                    to: (0, 0),
+                },
+            ],
+        },
+        Command::Move(Move::Nuke { i: 4, j: 5 }),
+        Command::Time(Duration::from_millis(1500)),
+        Command::Go,
+        Command::Quit,
+    ];
+    for command in commands {
+        let line = command.to_string();
+        assert!(!line.contains('\n'), "{line}");
+        assert_eq!(line.parse::<Command>(), Ok(command), "{line}");
+    }
+}
+
+#[test]
+fn replies_round_trip() {
+    let replies = [
+        Reply::Id("Some Engine 1.0".into()),
+        Reply::HelloOk,
+        Reply::ReadyOk,
+        Reply::BestMove(Move::TakeOverDraught {
+            from: (3, 3),
+            to: (5, 5),
+        }),
+        Reply::Info("depth 4 score -12".into()),
+    ];
+    for reply in replies {
+        let line = reply.to_string();
+        assert_eq!(line.parse::<Reply>(), Ok(reply), "{line}");
+    }
+}
+
+#[test]
+fn rejects_bad_lines() {
+    assert_eq!("".parse::<Command>(), Err(ProtocolError::Empty));
+    assert_eq!(
+        "play".parse::<Command>(),
+        Err(ProtocolError::Unknown("play".into()))
+    );
+    assert_eq!(
+        "move e4".parse::<Command>(),
+        Err(ProtocolError::Move("e4".into()))
+    );
+    assert!("position".parse::<Command>().is_err());
+    assert!("go now".parse::<Command>().is_err());
+    // rules are checked like the presets
+    assert!(matches!(
+        "rules (turn_structure: [[]])".parse::<Command>(),
+        Err(ProtocolError::Rules(_))
+    ));
+}
+
+#[test]
+fn reference_engine_plays_legal_moves() {
+    let mut engine =
+        SubprocessBot::spawn(env!("CARGO_BIN_EXE_engine"), Duration::from_secs(4)).unwrap();
+    engine.wait_ready().unwrap();
+    assert_eq!(engine.id(), "Watchtower Alpha-Beta");
+    assert_eq!(engine.name(), "Watchtower Alpha-Beta");
+
+    let mut game = GameLogic::with_rules(BoardConfig::new(9), RuleSet::default());
+    for _ in 0..6 {
+        let mv = loop {
+            if let Some(mv) = engine.poll(&game) {
+                break mv;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        };
+        assert!(game.legal_moves().contains(&mv), "{mv:?}");
+        game.apply(mv).unwrap();
+    }
+}
+
+#[test]
+fn silent_engine_resigns_when_its_clock_runs_out() {
+    // greets the game, then never answers `go`
+    let script = format!("{}/silent_engine.sh", env!("CARGO_TARGET_TMPDIR"));
+    fs::write(
+        &script,
+        "read line\necho 'id name Silent'\necho wtdok\nwhile read line; do :; done\n",
+    )
+    .unwrap();
+    let mut engine =
+        SubprocessBot::spawn(&format!("sh {script}"), Duration::from_millis(200)).unwrap();
+    engine.wait_ready().unwrap();
+
+    let game = GameLogic::with_rules(BoardConfig::new(9), RuleSet::default());
+    let mv = loop {
+        if let Some(mv) = engine.poll(&game) {
+            break mv;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    };
+    assert_eq!(mv, Move::Resign);
+}
+
+#[test]
+fn deaf_engine_doesnt_hold_up_the_game() {
+    // greets the game, then stops reading
+    let script = format!("{}/deaf_engine.sh", env!("CARGO_TARGET_TMPDIR"));
+    fs::write(
+        &script,
+        "read line\necho 'id name Deaf'\necho wtdok\nsleep 30\n",
+    )
+    .unwrap();
+    let mut engine =
+        SubprocessBot::spawn(&format!("sh {script}"), Duration::from_secs(60)).unwrap();
+    engine.wait_ready().unwrap();
+
+    // every other position is new, so the engine is sent the game each time, far more than
+    // a pipe holds
+    let start = GameLogic::with_rules(BoardConfig::new(9), RuleSet::default());
+    let mut next = start.clone();
+    next.apply(start.legal_moves()[0]).unwrap();
+    let started = Instant::now();
+    for n in 0..2000 {
+        let game = if n % 2 == 0 { &start } else { &next };
+        assert_eq!(engine.poll(game), None);
+    }
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn reference_engine_survives_bad_positions() {
+    let mut engine = Process::new(env!("CARGO_BIN_EXE_engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = engine.stdin.take().unwrap();
+    writeln!(stdin, "position 5\nisready\nquit").unwrap();
+
+    let replies: Vec<Reply> = BufReader::new(engine.stdout.take().unwrap())
+        .lines()
+        .map(|line| line.unwrap().parse().unwrap())
+        .collect();
+    assert!(matches!(replies[0], Reply::Info(_)), "{replies:?}");
+    assert_eq!(replies[1], Reply::ReadyOk);
+    engine.wait().unwrap();
+}
@@ -0,0 +1,31 @@
+//! Game records written and read back.
+
+use watchtower_defence::{
+    logic::{BoardConfig, GameLogic},
+    players::{Bot, RandomBot},
+    record::GameRecord,
+    rules::RuleSet,
+};
+
+#[test]
+fn replays_a_random_game() {
+    let rules = RuleSet {
+        move_limit: Some(40),
+        ..Default::default()
+    };
+    let mut game = GameLogic::with_rules(BoardConfig::new(9), rules);
+    let mut bot = RandomBot::seeded(7);
+    while game.result().is_none() {
+        let mv = bot.poll(&game).unwrap();
+        game.apply(mv).unwrap();
+    }
+
+    let record = GameRecord::new(&game, "Random", "Random");
+    let read = GameRecord::from_ron(&record.to_ron()).unwrap();
+    assert_eq!(read, record);
+
+    let replayed = read.replay().unwrap();
+    assert_eq!(replayed.hash(), game.hash());
+    assert_eq!(replayed.result(), game.result());
+    assert_eq!(replayed.ending(), game.ending());
+}
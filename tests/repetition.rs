@@ -0,0 +1,102 @@
+//! Positions coming back on the board, under both repetition rules.
+
+use watchtower_defence::{
+    board::{BoardState, Move, MoveError},
+    logic::{Ending, GameLogic, GameResult},
+    rules::Repetition,
+};
+
+const BACK_AND_FORTH: &str = "\
+. . . . . . . . b
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+w . . . . . . . .
+White to move, MoveDraught";
+
+fn back_and_forth(repetition: Repetition) -> GameLogic {
+    let mut board = BoardState::from_ascii(BACK_AND_FORTH).unwrap();
+    board.rules.repetition = repetition;
+    GameLogic::from_board(board)
+}
+
+/// Both draughts step out and back, passing the stone steps, which brings back the start.
+const ROUND_TRIP: [Move; 8] = [
+    Move::StepDraught {
+        from: (0, 0),
+        to: (1, 0),
+    },
+    Move::Pass,
+    Move::StepDraught {
+        from: (8, 8),
+        to: (7, 8),
+    },
+    Move::Pass,
+    Move::StepDraught {
+        from: (1, 0),
+        to: (0, 0),
+    },
+    Move::Pass,
+    Move::StepDraught {
+        from: (7, 8),
+        to: (8, 8),
+    },
+    Move::Pass,
+];
+
+#[test]
+fn drawn_after_the_third_time() {
+    let mut game = back_and_forth(Repetition::DrawAfter(3));
+
+    for mv in ROUND_TRIP {
+        game.apply(mv).unwrap();
+    }
+    assert_eq!(game.repetitions(), 2);
+    assert_eq!(game.ending(), None);
+
+    for mv in ROUND_TRIP {
+        game.apply(mv).unwrap();
+    }
+    assert_eq!(game.repetitions(), 3);
+    assert_eq!(game.ending(), Some(Ending::Repetition));
+    assert_eq!(game.result(), Some(GameResult::Draw));
+}
+
+#[test]
+fn forbidden_moves_bring_nothing_back() {
+    let mut game = back_and_forth(Repetition::Forbidden);
+
+    // the last pass of the round trip brings back the start, passes are never forbidden
+    for mv in ROUND_TRIP {
+        game.apply(mv).unwrap();
+    }
+    assert_eq!(game.repetitions(), 2);
+    assert_eq!(game.ending(), None);
+
+    let repeating = ROUND_TRIP[0];
+    assert!(game.repeats_position(repeating));
+    assert!(!game.legal_moves().contains(&repeating));
+    assert_eq!(
+        game.apply(repeating).err(),
+        Some(MoveError::RepeatedPosition)
+    );
+    assert_eq!(game.history().len(), ROUND_TRIP.len() + 1);
+
+    game.apply(Move::StepDraught {
+        from: (0, 0),
+        to: (0, 1),
+    })
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+//! Rule presets and the rules they are checked against.
+
+use std::{fs, path::Path};
+
+use watchtower_defence::rules::{RuleSet, RuleSetLoaderError};
+
+#[test]
+fn presets_can_be_read() {
+    for entry in fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/rules")).unwrap() {
+        let path = entry.unwrap().path();
+        if let Err(err) = RuleSet::read(&path) {
+            panic!("{}: {err}", path.display());
+        }
+    }
+    assert_eq!(RuleSet::from_ron(b"()").unwrap(), RuleSet::default());
+}
+
+#[test]
+fn rejects_rules_that_cant_be_played() {
+    let error = |text: &str| RuleSet::from_ron(text.as_bytes()).err();
+
+    assert!(matches!(
+        error("(nuke_radius: 19)"),
+        Some(RuleSetLoaderError::NukeRadius(19))
+    ));
+    assert!(error("(nuke_radius: 0)").is_none());
+    for n in [0, 362] {
+        assert!(matches!(
+            error(&format!("(min_region_stones: {n})")),
+            Some(RuleSetLoaderError::RegionStones(m)) if m == n
+        ));
+    }
+    for draughts in ["[(0, 1), (0, 0)]", "[(0, 1), (1, 0), (0, 1)]", "[(19, 0)]"] {
+        assert!(
+            matches!(
+                error(&format!("(watchtower_draughts: {draughts})")),
+                Some(RuleSetLoaderError::WatchtowerDraughts)
+            ),
+            "{draughts}"
+        );
+    }
+    assert!(matches!(
+        error("(watchtower_stones: [(0, -40)])"),
+        Some(RuleSetLoaderError::WatchtowerStones)
+    ));
+    assert!(matches!(
+        error("(min_watchtower_distance: 0)"),
+        Some(RuleSetLoaderError::WatchtowerDistance(0))
+    ));
+    assert!(matches!(
+        error("(repetition: DrawAfter(1))"),
+        Some(RuleSetLoaderError::Repetition(1))
+    ));
+    assert!(error("(repetition: DrawAfter(2))").is_none());
+    assert!(matches!(
+        error("(turn_structure: [[MoveDraught], []])"),
+        Some(RuleSetLoaderError::TurnStructure)
+    ));
+}
@@ -0,0 +1,74 @@
+//! Games saved halfway and continued.
+
+use std::path::Path;
+
+use watchtower_defence::{
+    logic::{BoardConfig, GameLogic},
+    players::{Bot, RandomBot},
+    record::RecordError,
+    rules::{RuleSet, RuleSetLoaderError},
+    save::{SaveError, SavedGame},
+};
+
+fn unfinished_game() -> GameLogic {
+    let mut game = GameLogic::with_rules(BoardConfig::new(9), RuleSet::default());
+    let mut bot = RandomBot::seeded(11);
+    for _ in 0..30 {
+        let mv = bot.poll(&game).unwrap();
+        game.apply(mv).unwrap();
+        if game.result().is_some() {
+            break;
+        }
+    }
+    game
+}
+
+#[test]
+fn continues_where_it_was_saved() {
+    let game = unfinished_game();
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("continue.save.ron");
+
+    SavedGame::new(&game, "Human", "MCTS").write(&path).unwrap();
+    let saved_game = SavedGame::read(&path).unwrap();
+    assert_eq!(saved_game.record.white, "Human");
+    assert_eq!(saved_game.record.black, "MCTS");
+
+    let resumed = saved_game.resume().unwrap();
+    assert_eq!(resumed.hash(), game.hash());
+    assert_eq!(resumed.next_state(), game.next_state());
+    assert_eq!(resumed.n_moves(), game.n_moves());
+}
+
+#[test]
+fn refuses_moves_leading_elsewhere() {
+    let game = unfinished_game();
+    let mut saved_game = SavedGame::new(&game, "Human", "Human");
+    saved_game.record.moves.pop();
+
+    let saved_game = SavedGame::from_ron(&saved_game.to_ron()).unwrap();
+    assert!(matches!(saved_game.resume(), Err(SaveError::Position)));
+}
+
+#[test]
+fn refuses_boards_that_cant_be_played() {
+    let mut saved_game = SavedGame::new(&unfinished_game(), "Human", "Human");
+    saved_game.record.size = 5;
+
+    assert!(matches!(
+        saved_game.resume(),
+        Err(SaveError::Record(RecordError::Size(5)))
+    ));
+}
+
+#[test]
+fn refuses_rules_that_cant_be_played() {
+    let mut saved_game = SavedGame::new(&unfinished_game(), "Human", "Human");
+    saved_game.record.rules.min_watchtower_distance = usize::MAX;
+
+    assert!(matches!(
+        saved_game.resume(),
+        Err(SaveError::Record(RecordError::Rules(
+            RuleSetLoaderError::WatchtowerDistance(usize::MAX)
+        )))
+    ));
+}
@@ -0,0 +1,74 @@
+//! Moves the search bot finds in small positions.
+
+use std::time::Duration;
+
+use watchtower_defence::{
+    board::{BoardState, Move},
+    logic::{BoardConfig, GameLogic, Side},
+    rules::RuleSet,
+    search::{evaluate, search, SearchConfig, WIN},
+};
+
+const CONFIG: SearchConfig = SearchConfig {
+    max_depth: 3,
+    time_limit: Duration::from_secs(60),
+};
+
+#[test]
+fn nukes_enemy_watchtower() {
+    let text = "\
+# White's draught on f6 is next to Black's watchtower on g7, nuking wins on the spot.
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . B . .
+
+. . . . . w . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . W . . . b . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+White to move, MoveDraught";
+    let game = GameLogic::from_board(BoardState::from_ascii(text).unwrap());
+
+    let result = search(&game, &CONFIG);
+    assert_eq!(result.best_move, Some(Move::Nuke { i: 5, j: 5 }));
+    assert!(result.score >= WIN - 1, "{result:?}");
+}
+
+#[test]
+fn evaluation_is_symmetric() {
+    let mut board = BoardState::with_rules(BoardConfig::new(13), RuleSet::default());
+    for mv in [
+        Move::PlaceWatchtower { i: 3, j: 3 },
+        Move::PlaceWatchtower { i: 9, j: 9 },
+        Move::StepDraught {
+            from: (4, 4),
+            to: (5, 5),
+        },
+    ] {
+        board.apply(mv).unwrap();
+    }
+
+    assert_eq!(
+        evaluate(&board, Side::White),
+        -evaluate(&board, Side::Black)
+    );
+}
+
+#[test]
+fn plays_legal_opening_moves() {
+    let mut game = GameLogic::with_rules(BoardConfig::new(9), RuleSet::default());
+    for _ in 0..6 {
+        let mv = search(&game, &CONFIG).best_move.unwrap();
+        assert!(game.legal_moves().contains(&mv), "{mv:?}");
+        game.apply(mv).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+//! Enclosed territory checked against hand drawn positions in `tests/fixtures/territory`.
+//!
+//! Fixtures draw the board with the top row first. Square rows and intersection rows alternate,
+//! squares are `.` or `+` when they are expected to be territory, intersections hold our stones
+//! `X`, enemy stones `O` that survive and enemy stones `o` that are expected to be captured.
+//! Lines starting with `#` are comments.
+
+use std::fs;
+use std::path::Path;
+
+use watchtower_defence::{
+    bitboard::Bitboard, logic::BoardConfig, rules::RuleSet, territory::enclosure,
+};
+
+struct Fixture {
+    size: usize,
+    ours: Bitboard,
+    enemy: Bitboard,
+    territory: Bitboard,
+    captured: Bitboard,
+}
+
+fn parse(text: &str) -> Fixture {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+    let size = lines.len().div_ceil(2);
+
+    let mut fixture = Fixture {
+        size,
+        ours: Bitboard::EMPTY,
+        enemy: Bitboard::EMPTY,
+        territory: Bitboard::EMPTY,
+        captured: Bitboard::EMPTY,
+    };
+
+    for (l, line) in lines.iter().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            if l % 2 == 0 {
+                let point = (column / 2, size - 1 - l / 2);
+                match c {
+                    '+' => fixture.territory.insert(point),
+                    '.' | ' ' => {}
+                    _ => panic!("unexpected square {c:?} on line {l}"),
+                }
+            } else {
+                let point = (column / 2, size - 2 - l / 2);
+                match c {
+                    'X' => fixture.ours.insert(point),
+                    'O' => fixture.enemy.insert(point),
+                    'o' => {
+                        fixture.enemy.insert(point);
+                        fixture.captured.insert(point);
+                    }
+                    ' ' => {}
+                    _ => panic!("unexpected intersection {c:?} on line {l}"),
+                }
+            }
+        }
+    }
+
+    fixture
+}
+
+fn render(size: usize, fixture: &Fixture, territory: Bitboard, captured: Bitboard) -> String {
+    let mut out = String::new();
+    for j in (0..size).rev() {
+        for i in 0..size {
+            out.push(if territory.contains((i, j)) { '+' } else { '.' });
+            out.push(' ');
+        }
+        out.push('\n');
+
+        if j == 0 {
+            break;
+        }
+        out.push(' ');
+        for i in 0..size - 1 {
+            let point = (i, j - 1);
+            out.push(if fixture.ours.contains(point) {
+                'X'
+            } else if captured.contains(point) {
+                'o'
+            } else if fixture.enemy.contains(point) {
+                'O'
+            } else {
+                ' '
+            });
+            out.push(' ');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn territory_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/territory");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    let rules = RuleSet::default();
+    let mut failed = vec![];
+
+    for path in paths {
+        let fixture = parse(&fs::read_to_string(&path).unwrap());
+        let enclosure = enclosure(BoardConfig::new(fixture.size), &rules, fixture.ours);
+        let captured = enclosure.intersections & fixture.enemy;
+
+        if enclosure.territory != fixture.territory || captured != fixture.captured {
+            println!(
+                "{}:\nexpected\n{}got\n{}",
+                path.display(),
+                render(fixture.size, &fixture, fixture.territory, fixture.captured),
+                render(fixture.size, &fixture, enclosure.territory, captured),
+            );
+            failed.push(path);
+        }
+    }
+
+    assert!(failed.is_empty(), "fixtures failed: {failed:?}");
+}
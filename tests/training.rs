@@ -0,0 +1,66 @@
+//! Training samples from self-play.
+
+use watchtower_defence::{
+    board::{BoardState, Move},
+    logic::{BoardConfig, GameLogic, GameResult, Side},
+    mcts::{MctsConfig, Rollout},
+    rules::RuleSet,
+    training::{Position, Sample, SelfPlay},
+};
+
+/// Points set in a plane.
+fn bits(plane: &str) -> u32 {
+    plane
+        .chars()
+        .map(|c| c.to_digit(16).unwrap().count_ones())
+        .sum()
+}
+
+#[test]
+fn planes_are_seen_from_the_side_to_move() {
+    let mut board = BoardState::with_rules(BoardConfig::new(9), RuleSet::default());
+    board.apply(Move::PlaceWatchtower { i: 2, j: 2 }).unwrap();
+
+    let position = Position::new(&board);
+    assert_eq!(position.side, Side::Black);
+    // 81 squares in 11 bytes, 64 intersections in 8
+    assert_eq!(position.draughts[0], "0".repeat(22));
+    assert_eq!(position.stones[0].len(), 16);
+    // White's watchtower on (2, 2) is bit 2 * 9 + 2 = 20 of the opponent's plane
+    assert_eq!(&position.watchtowers[1][..6], "000008");
+    assert_eq!(bits(&position.draughts[1]), 8);
+    assert_eq!(bits(&position.stones[1]), 12);
+}
+
+#[test]
+fn samples_carry_the_result() {
+    let rules = RuleSet {
+        move_limit: Some(6),
+        ..Default::default()
+    };
+    let config = MctsConfig {
+        playouts: 50,
+        rollout: Rollout::Random,
+        rollout_steps: 5,
+        trees: Some(1),
+        ..Default::default()
+    };
+    let (game, samples) =
+        SelfPlay::new(config, 2).play(GameLogic::with_rules(BoardConfig::new(9), rules));
+
+    assert_eq!(samples.len(), game.log().len());
+    for (sample, entry) in samples.iter().zip(game.log()) {
+        assert_eq!(sample.mv, entry.mv);
+        assert_eq!(sample.position.side, entry.turn.into());
+        let expected = match game.result().unwrap() {
+            GameResult::Winner(side) if side == sample.position.side => 1.,
+            GameResult::Winner(_) => 0.,
+            GameResult::Draw => 0.5,
+        };
+        assert_eq!(sample.result, expected);
+
+        let line = sample.to_json();
+        assert!(!line.contains('\n'));
+        assert_eq!(Sample::from_json(&line).unwrap(), *sample);
+    }
+}
@@ -0,0 +1,41 @@
+//! Position hashes.
+
+use watchtower_defence::{board::BoardState, zobrist};
+
+#[test]
+fn captured_draughts_change_the_hash() {
+    let board = BoardState::from_ascii(
+        "\
+. . . . . . . . b
+
+. . . . . . . . .
+
+. . W . . . B . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+. . . . . . . . .
+
+w . . . . . . . .
+White to move, MoveDraught",
+    )
+    .unwrap();
+
+    let mut captured = board.clone();
+    captured.white.captured_draughts = 1;
+    assert_ne!(zobrist::hash(&captured), zobrist::hash(&board));
+
+    let mut other_side = board.clone();
+    other_side.black.captured_draughts = 1;
+    assert_ne!(zobrist::hash(&other_side), zobrist::hash(&captured));
+
+    let mut same = board.clone();
+    same.white.captured_draughts = 1;
+    assert_eq!(zobrist::hash(&same), zobrist::hash(&captured));
+}